@@ -0,0 +1,17 @@
+use ComplexNumbersModule::*;
+
+fn main() {
+    println!(" Quaternions demonstration: {} * {} * {} = {}", -Quaternion::<f64>::newi(), Quaternion::<f64>::newj(), Quaternion::<f64>::newk(),
+        -Quaternion::<f64>::newi()*Quaternion::<f64>::newj()*Quaternion::<f64>::newk());
+    println!(" Complex numbers demonstration: {} * {} = {}", -ComplexNumber::i(), ComplexNumber::i(),
+        -ComplexNumber::i()*ComplexNumber::i());
+
+    println!(" Default <ComplexNumber> Matrix of size 4x4 :\n{}\n", Matrice::<ComplexNumber>::new_default(4, 4));
+    println!(" Default <Quaternion> Matrix of size 4x4 :\n{}\n", Matrice::<Quaternion>::new_default(4, 4));
+    println!(" Default <f64> Matrix of size 4x4 :\n{}\n", Matrice::<f64>::new_default(4, 4));
+    println!(" Default <i32> Matrix of size 4x4 :\n{}\n", Matrice::<i32>::new_default(4, 4));
+
+    println!("{} / {} = {}", complex!(12, 13), complex!(0, 3), (complex!(12, 13)/complex!(0, 3)));
+
+    println!("{}", quaternion!(0,0,0,0));
+}
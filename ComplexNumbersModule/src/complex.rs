@@ -0,0 +1,947 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use core::ops::Add;
+use core::ops::Sub;
+use std::ops::Mul;
+use std::ops::Div;
+use std::fmt::Debug;
+use std::ops::AddAssign;
+use std::ops::SubAssign;
+use std::ops::MulAssign;
+use std::ops::DivAssign;
+use std::iter::Sum;
+use std::iter::Product;
+use std::ops::Neg;
+use std::str::FromStr;
+use crate::traits::*;
+use crate::parse::*;
+
+//COMPLEX START
+pub struct Complex<T> {
+    pub Re: T,
+    pub Im: T
+}
+
+pub type ComplexNumber = Complex<f64>;
+
+#[macro_export]
+macro_rules! complex {
+    ($ex: expr, $ex2: expr) => {
+        $crate::complex::ComplexNumber {
+            Re: $ex as f64,
+            Im: $ex2 as f64
+        }
+    };
+
+    ($ex: expr, $ex2: expr; $t: ty) => {
+        $crate::complex::Complex::<$t> {
+            Re: $ex as $t,
+            Im: $ex2 as $t
+        }
+    };
+}
+
+impl<T: PartialEq> PartialEq for Complex<T> {
+    fn eq(&self, other: &Complex<T>) -> bool {
+        (self.Re == other.Re)&&(self.Im == other.Im)
+    }
+}
+
+impl<T: Copy> Copy for Complex<T> {
+}
+
+impl<T: Copy> Clone for Complex<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Ring> Add<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+    fn add(self, other: Complex<T>) -> Complex<T> {
+        Complex {
+            Re: self.Re + other.Re,
+            Im: self.Im + other.Im,
+        }
+    }
+}
+
+impl Add<ComplexNumber> for f64 {
+    type Output = ComplexNumber;
+    fn add(self, other: ComplexNumber) -> ComplexNumber {
+        ComplexNumber {
+            Re: self + other.Re,
+            Im: other.Im,
+        }
+    }
+}
+
+impl Add<Complex<f32>> for f32 {
+    type Output = Complex<f32>;
+    fn add(self, other: Complex<f32>) -> Complex<f32> {
+        Complex {
+            Re: self + other.Re,
+            Im: other.Im,
+        }
+    }
+}
+
+impl<T: Ring> Add<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn add(self, other: T) -> Complex<T> {
+        Complex {
+            Re: other + self.Re,
+            Im: self.Im,
+        }
+    }
+}
+
+impl<T: Ring> AddAssign for Complex<T> {
+    fn add_assign(&mut self, other: Complex<T>) {
+        *self = Complex {
+            Re: self.Re + other.Re,
+            Im: self.Im + other.Im
+        };
+    }
+}
+
+impl<T: Ring> Sub<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+    fn sub(self, other: Complex<T>) -> Complex<T> {
+        Complex {
+            Re: self.Re - other.Re,
+            Im: self.Im - other.Im,
+        }
+    }
+}
+
+impl<T: Ring> Mul<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+    fn mul(self, other: Complex<T>) -> Complex<T> {
+        Complex {
+            Re: self.Re*other.Re - self.Im*other.Im,
+            Im: self.Re*other.Im + self.Im*other.Re
+        }
+    }
+}
+
+impl<T: Real> Div<Complex<T>> for Complex<T> {
+    type Output = Complex<T>;
+    fn div(self, other: Complex<T>) -> Complex<T> {
+        //Smith's algorithm: scale by the larger divisor component instead of
+        //dividing by c*c + d*d, which overflows or underflows far too early
+        let (zero, one) = (T::zero(), T::one());
+        let absolute = |x: T| {if x >= zero {x} else {-x}};
+        let (a, b, c, d) = (self.Re, self.Im, other.Re, other.Im);
+        let (re, im) = if absolute(c) >= absolute(d) {
+            let r = d / c;
+            let den = c + d*r;
+            ((a + b*r) / den, (b - a*r) / den)
+        } else {
+            let r = c / d;
+            let den = c*r + d;
+            ((a*r + b) / den, (b*r - a) / den)
+        };
+        if !(re.is_nan() || im.is_nan()) {
+            return Complex {Re: re, Im: im};
+        }
+        //inf - inf or 0 * inf along the way, recover infinities and zeros like C99 annex G
+        let unit = |x: T| {(if x.is_infinite() {one} else {zero}).copysign(x)};
+        if c == zero && d == zero && !(a.is_nan() && b.is_nan()) {
+            let inf = T::infinity().copysign(c);
+            Complex {Re: inf*a, Im: inf*b}
+        } else if (a.is_infinite() || b.is_infinite()) && c.is_finite() && d.is_finite() {
+            let (a, b) = (unit(a), unit(b));
+            Complex {Re: T::infinity()*(a*c + b*d), Im: T::infinity()*(b*c - a*d)}
+        } else if (c.is_infinite() || d.is_infinite()) && a.is_finite() && b.is_finite() {
+            let (c, d) = (unit(c), unit(d));
+            Complex {Re: zero*(a*c + b*d), Im: zero*(b*c - a*d)}
+        } else {
+            Complex {Re: re, Im: im}
+        }
+    }
+}
+
+impl<T: Ring + Neg<Output = T>> Neg for Complex<T> {
+    type Output = Complex<T>;
+    fn neg(self) -> Complex<T> {
+        Complex {
+            Re: -self.Re,
+            Im: -self.Im
+        }
+    }
+}
+
+impl<T: Ring> Sub<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn sub(self, other: T) -> Complex<T> {
+        Complex {
+            Re: self.Re - other,
+            Im: self.Im
+        }
+    }
+}
+
+impl<T: Ring> Mul<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn mul(self, other: T) -> Complex<T> {
+        Complex {
+            Re: self.Re * other,
+            Im: self.Im * other
+        }
+    }
+}
+
+impl<T: Field> Div<T> for Complex<T> {
+    type Output = Complex<T>;
+    fn div(self, other: T) -> Complex<T> {
+        Complex {
+            Re: self.Re / other,
+            Im: self.Im / other
+        }
+    }
+}
+
+impl Sub<ComplexNumber> for f64 {
+    type Output = ComplexNumber;
+    fn sub(self, other: ComplexNumber) -> ComplexNumber {
+        Complex {
+            Re: self - other.Re,
+            Im: -other.Im
+        }
+    }
+}
+
+impl Mul<ComplexNumber> for f64 {
+    type Output = ComplexNumber;
+    fn mul(self, other: ComplexNumber) -> ComplexNumber {
+        Complex {
+            Re: self * other.Re,
+            Im: self * other.Im
+        }
+    }
+}
+
+impl Div<ComplexNumber> for f64 {
+    type Output = ComplexNumber;
+    fn div(self, other: ComplexNumber) -> ComplexNumber {
+        Complex {
+            Re: self,
+            Im: 0.0
+        } / other
+    }
+}
+
+impl Sub<Complex<f32>> for f32 {
+    type Output = Complex<f32>;
+    fn sub(self, other: Complex<f32>) -> Complex<f32> {
+        Complex {
+            Re: self - other.Re,
+            Im: -other.Im
+        }
+    }
+}
+
+impl Mul<Complex<f32>> for f32 {
+    type Output = Complex<f32>;
+    fn mul(self, other: Complex<f32>) -> Complex<f32> {
+        Complex {
+            Re: self * other.Re,
+            Im: self * other.Im
+        }
+    }
+}
+
+impl Div<Complex<f32>> for f32 {
+    type Output = Complex<f32>;
+    fn div(self, other: Complex<f32>) -> Complex<f32> {
+        Complex {
+            Re: self,
+            Im: 0.0
+        } / other
+    }
+}
+
+impl<T: Ring> AddAssign<T> for Complex<T> {
+    fn add_assign(&mut self, other: T) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Ring> SubAssign for Complex<T> {
+    fn sub_assign(&mut self, other: Complex<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Ring> SubAssign<T> for Complex<T> {
+    fn sub_assign(&mut self, other: T) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Ring> MulAssign for Complex<T> {
+    fn mul_assign(&mut self, other: Complex<T>) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Ring> MulAssign<T> for Complex<T> {
+    fn mul_assign(&mut self, other: T) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Real> DivAssign for Complex<T> {
+    fn div_assign(&mut self, other: Complex<T>) {
+        *self = *self / other;
+    }
+}
+
+impl<T: Field> DivAssign<T> for Complex<T> {
+    fn div_assign(&mut self, other: T) {
+        *self = *self / other;
+    }
+}
+
+forward_ref_binop!([T: Ring] Add, add for Complex<T>);
+forward_ref_binop!([T: Ring] Sub, sub for Complex<T>);
+forward_ref_binop!([T: Ring] Mul, mul for Complex<T>);
+forward_ref_binop!([T: Real] Div, div for Complex<T>);
+forward_ref_binop!([T: Ring] Add, add for Complex<T>, T => Complex<T>);
+forward_ref_binop!([T: Ring] Sub, sub for Complex<T>, T => Complex<T>);
+forward_ref_binop!([T: Ring] Mul, mul for Complex<T>, T => Complex<T>);
+forward_ref_binop!([T: Field] Div, div for Complex<T>, T => Complex<T>);
+forward_ref_binop!([] Add, add for f64, ComplexNumber => ComplexNumber);
+forward_ref_binop!([] Sub, sub for f64, ComplexNumber => ComplexNumber);
+forward_ref_binop!([] Mul, mul for f64, ComplexNumber => ComplexNumber);
+forward_ref_binop!([] Div, div for f64, ComplexNumber => ComplexNumber);
+forward_ref_binop!([] Add, add for f32, Complex<f32> => Complex<f32>);
+forward_ref_binop!([] Sub, sub for f32, Complex<f32> => Complex<f32>);
+forward_ref_binop!([] Mul, mul for f32, Complex<f32> => Complex<f32>);
+forward_ref_binop!([] Div, div for f32, Complex<f32> => Complex<f32>);
+forward_ref_unop!([T: Ring + Neg<Output = T>] Neg, neg for Complex<T>);
+forward_ref_op_assign!([T: Ring] AddAssign, add_assign for Complex<T>, Complex<T>);
+forward_ref_op_assign!([T: Ring] SubAssign, sub_assign for Complex<T>, Complex<T>);
+forward_ref_op_assign!([T: Ring] MulAssign, mul_assign for Complex<T>, Complex<T>);
+forward_ref_op_assign!([T: Real] DivAssign, div_assign for Complex<T>, Complex<T>);
+forward_ref_op_assign!([T: Ring] AddAssign, add_assign for Complex<T>, T);
+forward_ref_op_assign!([T: Ring] SubAssign, sub_assign for Complex<T>, T);
+forward_ref_op_assign!([T: Ring] MulAssign, mul_assign for Complex<T>, T);
+forward_ref_op_assign!([T: Field] DivAssign, div_assign for Complex<T>, T);
+
+impl<T: Ring> Sum for Complex<T> {
+    fn sum<I: Iterator<Item = Complex<T>>>(iter: I) -> Complex<T> {
+        iter.fold(<Complex<T> as Ring>::zero(), |a, b| a + b)
+    }
+}
+
+impl<'a, T: Ring> Sum<&'a Complex<T>> for Complex<T> {
+    fn sum<I: Iterator<Item = &'a Complex<T>>>(iter: I) -> Complex<T> {
+        iter.fold(<Complex<T> as Ring>::zero(), |a, b| a + *b)
+    }
+}
+
+impl<T: Ring> Product for Complex<T> {
+    fn product<I: Iterator<Item = Complex<T>>>(iter: I) -> Complex<T> {
+        iter.fold(<Complex<T> as Ring>::one(), |a, b| a * b)
+    }
+}
+
+impl<'a, T: Ring> Product<&'a Complex<T>> for Complex<T> {
+    fn product<I: Iterator<Item = &'a Complex<T>>>(iter: I) -> Complex<T> {
+        iter.fold(<Complex<T> as Ring>::one(), |a, b| a * *b)
+    }
+}
+
+impl<T: Ring> Default for Complex<T> {
+    fn default() -> Self {
+        Complex {
+            Re: T::zero(),
+            Im: T::one()
+        }
+    }
+}
+
+impl<T: Ring> Ring for Complex<T> {
+    fn zero() -> Self {
+        Complex {
+            Re: T::zero(),
+            Im: T::zero()
+        }
+    }
+
+    fn one() -> Self {
+        Complex {
+            Re: T::one(),
+            Im: T::zero()
+        }
+    }
+}
+
+impl<T: Real> DivisionRing for Complex<T> {
+    type Real = T;
+
+    fn recip(self) -> Self {
+        <Self as Ring>::one() / self
+    }
+
+    fn conj(self) -> Self {
+        self.conjugate()
+    }
+
+    fn norm_sqr(self) -> T {
+        Complex::norm_sqr(&self)
+    }
+
+    fn map_parts(self, f: impl Fn(T) -> T) -> Self {
+        self.apply(f)
+    }
+
+    fn abs(self) -> T {
+        self.range()
+    }
+
+    fn div_right(self, other: Self) -> Self {
+        self / other
+    }
+
+    fn div_left(self, other: Self) -> Self {
+        self / other
+    }
+}
+
+impl<T: Real> Field for Complex<T> {}
+
+impl<T: Real> ComplexField for Complex<T> {
+    fn from_real(x: T) -> Self {
+        Complex {
+            Re: x,
+            Im: T::zero()
+        }
+    }
+
+    fn re(self) -> T {
+        self.Re
+    }
+
+    fn im(self) -> T {
+        self.Im
+    }
+}
+
+impl<T: Ring + PartialOrd + Display> Display for Complex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (zero, one) = (T::zero(), T::one());
+        let absolute = |x: T| {if x < zero {zero - x} else {x}};
+        let mut text = String::new();
+        if self.Re != zero || self.Im == zero {
+            text = format!("{}",self.Re);
+        }
+        if self.Im != zero {
+            text = format!("{}{}{}i",text,
+                if self.Re != zero {if self.Im > zero{"+"}else {"-"}}
+                else {if self.Im > zero{""}else {"-"}} ,
+                if absolute(self.Im) != one {absolute(self.Im).to_string()}
+                else {"".to_string()});
+        }
+        write!(f, "{}", text)
+    }
+}
+
+impl<T: Ring + PartialOrd + Display> Debug for Complex<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (zero, one) = (T::zero(), T::one());
+        let absolute = |x: T| {if x < zero {zero - x} else {x}};
+        let mut text = String::new();
+        if self.Re != zero || self.Im == zero {
+            text = format!("{}",self.Re);
+        }
+        if self.Im != zero {
+            text = format!("{}{}{}i",text,
+                if self.Re != zero {if self.Im > zero{"+"}else {"-"}}
+                else {if self.Im > zero{""}else {"-"}} ,
+                if absolute(self.Im) != one {absolute(self.Im).to_string()}
+                else {"".to_string()});
+        }
+        write!(f, "{}", text)
+    }
+}
+
+impl<T: Real + FromStr> FromStr for Complex<T> {
+    type Err = ParseNumberError;
+    //reads what Display prints, j is accepted in place of i
+    fn from_str(s: &str) -> std::result::Result<Complex<T>, ParseNumberError> {
+        let parts = parse_parts::<T>(s, &[('i', 1), ('j', 1)])?;
+        Ok(Complex {
+            Re: parts[0],
+            Im: parts[1]
+        })
+    }
+}
+
+impl<T: Real> Vectorable for Complex<T> {
+    fn vectorify(&self) -> Vec<f64> {
+        vec![self.Re.to_f64(), self.Im.to_f64() ]
+    }
+}
+
+impl<T: Ring> Complex<T> {
+
+    pub fn new() -> Self {
+        Self{
+            Re: T::zero(),
+            Im: T::zero()
+        }
+    }
+
+    pub fn i() -> Self {
+        Self {
+            Re: T::zero(),
+            Im: T::one()
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            Re: T::one(),
+            Im: T::zero()
+        }
+    }
+
+    pub fn real(&self) -> bool {
+        self.Im != T::zero()
+    }
+
+    pub fn norm_sqr(&self) -> T {
+        //exact for integer components
+        self.Re*self.Re + self.Im*self.Im
+    }
+
+    pub fn add(&self, other: Self) -> Self {
+        Self {
+            Re: self.Re + other.Re,
+            Im: self.Im + other.Im
+        }
+    }
+
+    pub fn mul(&self, other: Self) -> Self {
+        //(a+bi)(c+di) = (ac - bd) + (ad + bc)
+        Self {
+            Re: self.Re*other.Re - self.Im*other.Im,
+            Im: self.Re*other.Im + self.Im*other.Re
+        }
+    }
+
+    pub fn apply(&self, f: impl Fn(T) -> T) -> Self {
+        Self {
+            Re: f(self.Re),
+            Im: f(self.Im)
+        }
+    }
+
+    pub fn cprint(&self) where T: Display {
+        println!("{} {}", self.Re, self.Im);
+    }
+}
+
+impl<T: Ring + Neg<Output = T>> Complex<T> {
+    pub fn conjugate(&self) -> Self {
+        Self {
+            Re: self.Re,
+            Im: -self.Im
+        }
+    }
+
+    pub fn mul_i(&self) -> Self {
+        //i*(a+bi) = -b + ai, done by hand so signed zeros survive
+        Self {
+            Re: -self.Im,
+            Im: self.Re
+        }
+    }
+
+    pub fn div_i(&self) -> Self {
+        //(a+bi)/i = b - ai
+        Self {
+            Re: self.Im,
+            Im: -self.Re
+        }
+    }
+}
+
+impl ComplexNumber {
+    pub fn max() -> Self {
+        Self {
+            Re: f64::MAX,
+            Im: f64::MAX
+        }
+    }
+
+    pub fn polar(&self) -> Polar {
+        Polar::new(self.range(), self.arg())
+    }
+}
+
+impl<T: Real> Complex<T> {
+    pub fn abs(self) -> T {
+        //hypot does not overflow for large components or flush small ones to zero
+        self.Re.hypot(self.Im)
+    }
+
+    pub fn range(&self) -> T {
+        self.abs()
+    }
+
+    //None for zero, where 1 / self would be inf or NaN
+    pub fn checked_inverse(&self) -> Option<Self> {
+        if self.Re == T::zero() && self.Im == T::zero() {
+            None
+        } else {
+            Some(<Self as Ring>::one() / *self)
+        }
+    }
+
+    pub fn div(&self, other: Self) -> Option<Self> {
+        //the / operator's quotient, infinities included, or None where a part of it is NaN
+        //(0/0, inf/inf, NaN input, or x/0 leaving inf*0 in one part), so Some(q) always means q == self / other
+        let quotient = *self / other;
+        if quotient.Re.is_nan() || quotient.Im.is_nan() {
+            None
+        } else {
+            Some(quotient)
+        }
+    }
+
+    pub fn angle(&self) -> T {
+        self.arg()
+    }
+
+    pub fn arg(&self) -> T {
+        //principal argument, in (-pi, pi]
+        self.Im.atan2(self.Re)
+    }
+
+    pub fn to_polar(self) -> (T, T) {
+        (self.range(), self.arg())
+    }
+
+    pub fn from_polar(r: T, theta: T) -> Self {
+        Self {
+            Re: r * theta.cos(),
+            Im: r * theta.sin()
+        }
+    }
+
+    //ELEMENTARY FUNCTIONS
+    //branch cuts follow the C99 conventions: the sign of a zero imaginary part
+    //decides which side of a cut a point on it belongs to
+
+    pub fn exp(&self) -> Self {
+        //e^(a+bi) = e^a * (cos b + i sin b)
+        if self.Im == T::zero() {
+            return Self {
+                Re: self.Re.exp(),
+                Im: self.Im
+            };
+        }
+        Self::from_polar(self.Re.exp(), self.Im)
+    }
+
+    pub fn ln(&self) -> Self {
+        //principal branch, cut along the negative real axis, Im in (-pi, pi]
+        Self {
+            Re: self.Re.hypot(self.Im).ln(),
+            Im: self.arg()
+        }
+    }
+
+    pub fn sqrt(&self) -> Self {
+        //principal branch, cut along the negative real axis, Re >= 0
+        let (zero, two) = (T::zero(), T::from_f64(2.0));
+        if self.Re == zero && self.Im == zero {
+            return Self {
+                Re: zero,
+                Im: self.Im
+            };
+        }
+        let r = self.Re.hypot(self.Im);
+        if self.Re.is_finite() && self.Im.is_finite() && !(r + self.Re.copysign(T::one())).is_finite() {
+            //r + |Re| overflows near the top of the range, sqrt(z) = 2 sqrt(z / 4)
+            let quarter = T::from_f64(0.25);
+            let root = Self {Re: self.Re * quarter, Im: self.Im * quarter}.sqrt();
+            return Self {Re: two * root.Re, Im: two * root.Im};
+        }
+        if self.Re >= zero {
+            let t = ((r + self.Re) / two).sqrt();
+            Self {
+                Re: t,
+                Im: self.Im / (two * t)
+            }
+        } else {
+            let t = ((r - self.Re) / two).sqrt();
+            Self {
+                Re: self.Im.copysign(T::one()) / (two * t),
+                Im: t.copysign(self.Im)
+            }
+        }
+    }
+
+    pub fn cbrt(&self) -> Self {
+        //principal branch (so cbrt(-8) = 1+1.732..i, not -2), cut along the negative real axis
+        if self.Re == T::zero() && self.Im == T::zero() {
+            return *self;
+        }
+        Self::from_polar(self.Re.hypot(self.Im).cbrt(), self.arg() / T::from_f64(3.0))
+    }
+
+    pub fn powi(&self, n: i32) -> Self {
+        //exponentiation by squaring, z^-n = 1/z^n
+        let mut base = *self;
+        let mut exp = n.unsigned_abs();
+        let mut result = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        if n < 0 {Self::one() / result} else {result}
+    }
+
+    pub fn powf(&self, x: T) -> Self {
+        //principal value exp(x * ln z)
+        let zero = T::zero();
+        if self.Re == zero && self.Im == zero {
+            return if x == zero {Self::one()}
+                else if x > zero {Self::new()}
+                else {Self {Re: T::infinity(), Im: zero}};
+        }
+        let (r, theta) = self.to_polar();
+        Self::from_polar(r.powf(x), theta * x)
+    }
+
+    pub fn powc(&self, w: Self) -> Self {
+        //principal value exp(w * ln z)
+        let zero = T::zero();
+        if self.Re == zero && self.Im == zero {
+            return if w.Re == zero && w.Im == zero {Self::one()}
+                else if w.Re > zero {Self::new()}
+                else {Self {Re: T::nan(), Im: T::nan()}};
+        }
+        (w * self.ln()).exp()
+    }
+
+    pub fn sin(&self) -> Self {
+        //sin(a+bi) = sin a cosh b + i cos a sinh b
+        Self {
+            Re: self.Re.sin() * self.Im.cosh(),
+            Im: self.Re.cos() * self.Im.sinh()
+        }
+    }
+
+    pub fn cos(&self) -> Self {
+        //cos(a+bi) = cos a cosh b - i sin a sinh b
+        Self {
+            Re: self.Re.cos() * self.Im.cosh(),
+            Im: -(self.Re.sin() * self.Im.sinh())
+        }
+    }
+
+    pub fn tan(&self) -> Self {
+        //tan z = -i tanh(iz)
+        self.mul_i().tanh().div_i()
+    }
+
+    pub fn sinh(&self) -> Self {
+        //sinh(a+bi) = sinh a cos b + i cosh a sin b
+        Self {
+            Re: self.Re.sinh() * self.Im.cos(),
+            Im: self.Re.cosh() * self.Im.sin()
+        }
+    }
+
+    pub fn cosh(&self) -> Self {
+        //cosh(a+bi) = cosh a cos b + i sinh a sin b
+        Self {
+            Re: self.Re.cosh() * self.Im.cos(),
+            Im: self.Re.sinh() * self.Im.sin()
+        }
+    }
+
+    pub fn tanh(&self) -> Self {
+        //tanh(a+bi) = (sinh 2a + i sin 2b) / (cosh 2a + cos 2b)
+        let two = T::from_f64(2.0);
+        if self.Re.copysign(T::one()) > T::from_f64(20.0) {
+            //cosh 2a dominates, the result is +-1 up to rounding
+            return Self {
+                Re: T::one().copysign(self.Re),
+                Im: T::zero().copysign((two * self.Im).sin())
+            };
+        }
+        let d = (two * self.Re).cosh() + (two * self.Im).cos();
+        Self {
+            Re: (two * self.Re).sinh() / d,
+            Im: (two * self.Im).sin() / d
+        }
+    }
+
+    pub fn asin(&self) -> Self {
+        //Kahan's form, Re = atan(x / Re(sqrt(1 - z) sqrt(1 + z))) and Im = asinh(Im(conj(sqrt(1 - z)) sqrt(1 + z))),
+        //no cancellation near 0 and the signed zeros of 1 - z and 1 + z pick the side of the cuts
+        //cuts along the real axis outside [-1, 1]
+        let (a, b) = (self.one_minus().sqrt(), self.one_plus().sqrt());
+        Self {
+            Re: self.Re.atan2(a.Re*b.Re - a.Im*b.Im),
+            Im: (a.Re*b.Im - a.Im*b.Re).asinh()
+        }
+    }
+
+    pub fn acos(&self) -> Self {
+        //Kahan's form, Re = 2 atan(Re(sqrt(1 - z)) / Re(sqrt(1 + z))) and Im = asinh(Im(conj(sqrt(1 + z)) sqrt(1 - z))),
+        //accurate near 1 where pi/2 - asin z cancels, cuts along the real axis outside [-1, 1]
+        let (a, b) = (self.one_minus().sqrt(), self.one_plus().sqrt());
+        Self {
+            Re: T::from_f64(2.0) * a.Re.atan2(b.Re),
+            Im: (b.Re*a.Im - b.Im*a.Re).asinh()
+        }
+    }
+
+    pub fn atan(&self) -> Self {
+        //atan z = -i atanh(iz), cuts along the imaginary axis outside [-i, i]
+        self.mul_i().atanh().div_i()
+    }
+
+    pub fn asinh(&self) -> Self {
+        //asinh z = -i asin(iz), cuts along the imaginary axis outside [-i, i]
+        if self.Im == T::zero() {
+            return Self {
+                Re: self.Re.asinh(),
+                Im: self.Im
+            };
+        }
+        self.mul_i().asin().div_i()
+    }
+
+    pub fn acosh(&self) -> Self {
+        //Kahan's form, Re = asinh(Re(conj(sqrt(z - 1)) sqrt(z + 1))) and Im = 2 atan(Im(sqrt(z - 1)) / Re(sqrt(z + 1))),
+        //cut along the real axis left of 1
+        let (a, b) = (self.one_minus().apply(|x| {-x}).sqrt(), self.one_plus().sqrt());
+        Self {
+            Re: (a.Re*b.Re + a.Im*b.Im).asinh(),
+            Im: T::from_f64(2.0) * a.Im.atan2(b.Re)
+        }
+    }
+
+    pub fn atanh(&self) -> Self {
+        //atanh z = (ln(1 + z) - ln(1 - z)) / 2 with the two logarithms merged so nothing cancels near 0,
+        //Re = ln_1p(4x / ((1 - x)^2 + y^2)) / 4 and Im = atan2(2y, (1 - x)(1 + x) - y^2) / 2
+        //cuts along the real axis outside [-1, 1]
+        let (one, two, four) = (T::one(), T::from_f64(2.0), T::from_f64(4.0));
+        let (x, y) = (self.Re, self.Im);
+        let d = (one - x)*(one - x) + y*y;
+        Self {
+            Re: (four*x / d).ln_1p() / four,
+            Im: (two*y).atan2((one - x)*(one + x) - y*y) / two
+        }
+    }
+
+    //1 - z and 1 + z keeping the sign of a zero imaginary part, which picks the side of a branch cut
+    fn one_minus(&self) -> Self {
+        Self {
+            Re: T::one() - self.Re,
+            Im: -self.Im
+        }
+    }
+
+    fn one_plus(&self) -> Self {
+        Self {
+            Re: T::one() + self.Re,
+            Im: self.Im
+        }
+    }
+
+}
+//COMPLEX END
+
+//POLAR START
+pub struct Polar {
+    pub r: f64,
+    pub theta: f64
+}
+
+pub fn principal_angle<T: Real>(theta: T) -> T {
+    //wraps theta into (-pi, pi]
+    let pi = T::pi();
+    let two_pi = T::from_f64(2.0) * pi;
+    let wrapped = theta - two_pi * ((theta + pi) / two_pi).floor();
+    //rounding can leave a value a hair past either end
+    if wrapped <= -pi {wrapped + two_pi} else if wrapped > pi {wrapped - two_pi} else {wrapped}
+}
+
+impl Copy for Polar {}
+
+impl Clone for Polar {
+    fn clone(&self) -> Polar {
+        *self
+    }
+}
+
+impl PartialEq for Polar {
+    fn eq(&self, other: &Polar) -> bool {
+        (self.r == other.r)&&(self.theta == other.theta)
+    }
+}
+
+impl Mul<Polar> for Polar {
+    type Output = Polar;
+    fn mul(self, other: Polar) -> Polar {
+        Polar::new(self.r * other.r, self.theta + other.theta)
+    }
+}
+
+impl Div<Polar> for Polar {
+    type Output = Polar;
+    fn div(self, other: Polar) -> Polar {
+        Polar::new(self.r / other.r, self.theta - other.theta)
+    }
+}
+
+impl Display for Polar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}∠{}", self.r, self.theta)
+    }
+}
+
+impl Debug for Polar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}∠{}", self.r, self.theta)
+    }
+}
+
+impl Polar {
+    pub fn new(r: f64, theta: f64) -> Self {
+        //keeps r non-negative and theta on the principal branch
+        if r < 0.0 {
+            return Self {
+                r: -r,
+                theta: principal_angle(theta + std::f64::consts::PI)
+            };
+        }
+        Self {
+            r,
+            theta: principal_angle(theta)
+        }
+    }
+
+    pub fn to_cartesian(self) -> ComplexNumber {
+        ComplexNumber::from_polar(self.r, self.theta)
+    }
+
+    pub fn powi(&self, n: i32) -> Self {
+        Polar::new(self.r.powi(n), self.theta * n as f64)
+    }
+}
+//POLAR END
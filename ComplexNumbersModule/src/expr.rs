@@ -0,0 +1,862 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::fmt::Debug;
+use crate::complex::*;
+use crate::quaternion::Quaternion;
+use crate::matrix::*;
+
+//VALUES START
+//everything an expression can evaluate to, reals are complex numbers with no imaginary part
+pub enum Value {
+    Complex(ComplexNumber),
+    Quaternion(Quaternion),
+    Matrix(Matrix)
+}
+
+pub enum Type {
+    Complex,
+    Quaternion,
+    Matrix
+}
+
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        match self {
+            Value::Complex(z) => Value::Complex(*z),
+            Value::Quaternion(q) => Value::Quaternion(*q),
+            Value::Matrix(m) => Value::Matrix(m.clone())
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Value::Complex(z) => write!(f, "{}", z),
+            Value::Quaternion(q) => write!(f, "{}", q),
+            //Display for matrices ends every row with a newline
+            Value::Matrix(m) => write!(f, "{}", m.to_string().trim_end())
+        }
+    }
+}
+
+impl Debug for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl Value {
+    pub fn type_of(&self) -> Type {
+        match self {
+            Value::Complex(_) => Type::Complex,
+            Value::Quaternion(_) => Type::Quaternion,
+            Value::Matrix(_) => Type::Matrix
+        }
+    }
+
+    pub fn real(&self) -> Option<f64> {
+        match self {
+            Value::Complex(z) if z.Im == 0.0 => Some(z.Re),
+            Value::Quaternion(q) if q.real() => Some(q.Re),
+            _ => None
+        }
+    }
+
+    pub fn to_quaternion(&self) -> Option<Quaternion> {
+        match self {
+            Value::Complex(z) => Some(quaternion!(z.Re, z.Im, 0, 0)),
+            Value::Quaternion(q) => Some(*q),
+            Value::Matrix(_) => None
+        }
+    }
+}
+
+impl Clone for Type {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for Type {}
+
+impl PartialEq for Type {
+    fn eq(&self, other: &Type) -> bool {
+        matches!((self, other), (Type::Complex, Type::Complex) | (Type::Quaternion, Type::Quaternion) | (Type::Matrix, Type::Matrix))
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Type::Complex => write!(f, "number"),
+            Type::Quaternion => write!(f, "quaternion"),
+            Type::Matrix => write!(f, "matrix")
+        }
+    }
+}
+
+impl Debug for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+//VALUES END
+
+//ERRORS START
+//positions are 0-based character offsets into the parsed string
+pub enum ExprError {
+    UnexpectedChar { position: usize, found: char },
+    UnexpectedToken { position: usize, found: String },
+    UnexpectedEnd { position: usize },
+    InvalidNumber { position: usize, text: String },
+    UnknownVariable { position: usize, name: String },
+    UnknownFunction { position: usize, name: String },
+    ArgumentCount { position: usize, name: String, expected: usize, found: usize },
+    ArgumentType { position: usize, name: String, found: Vec<Type> },
+    TypeMismatch { position: usize, op: char, left: Type, right: Type },
+    MatrixEntry { position: usize, found: Type },
+    Matrix { position: usize, error: MatrixError },
+    Domain { position: usize, message: String }
+}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ExprError::UnexpectedChar { position, found } =>
+                write!(f, "unexpected '{}' at {}", found, position),
+            ExprError::UnexpectedToken { position, found } =>
+                write!(f, "unexpected {} at {}", found, position),
+            ExprError::UnexpectedEnd { position } =>
+                write!(f, "unexpected end of input at {}", position),
+            ExprError::InvalidNumber { position, text } =>
+                write!(f, "invalid number \"{}\" at {}", text, position),
+            ExprError::UnknownVariable { position, name } =>
+                write!(f, "unknown variable {} at {}", name, position),
+            ExprError::UnknownFunction { position, name } =>
+                write!(f, "unknown function {}() at {}", name, position),
+            ExprError::ArgumentCount { position, name, expected, found } =>
+                write!(f, "{}() takes {} argument{}, got {} at {}", name, expected, if *expected == 1 {""} else {"s"}, found, position),
+            ExprError::ArgumentType { position, name, found } => {
+                let found: Vec<String> = found.iter().map(|t| t.to_string()).collect();
+                write!(f, "{}() is not defined for ({}) at {}", name, found.join(", "), position)
+            },
+            ExprError::TypeMismatch { position, op, left, right } =>
+                write!(f, "cannot apply '{}' to a {} and a {} at {}", op, left, right, position),
+            ExprError::MatrixEntry { position, found } =>
+                write!(f, "matrix entries must be real, got a {} at {}", found, position),
+            ExprError::Matrix { position, error } =>
+                write!(f, "{} at {}", error, position),
+            ExprError::Domain { position, message } =>
+                write!(f, "{} at {}", message, position)
+        }
+    }
+}
+
+impl Debug for ExprError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for ExprError {}
+//ERRORS END
+
+//FUNCTIONS START
+pub type Typing = Box<dyn Fn(&[Type]) -> Option<Type>>;
+pub type Call = Box<dyn Fn(&[Value]) -> std::result::Result<Value, String>>;
+
+//a function callable from expressions, typing gives the result type for the
+//argument types or None if the call is not defined for them
+pub struct Function {
+    pub arity: usize,
+    pub typing: Typing,
+    pub call: Call
+}
+
+pub struct Functions {
+    table: HashMap<String, Function>
+}
+
+impl Default for Functions {
+    fn default() -> Self {
+        Functions::standard()
+    }
+}
+
+impl Functions {
+    //no functions at all
+    pub fn new() -> Self {
+        Functions {
+            table: HashMap::new()
+        }
+    }
+
+    //conj, norm, abs, inverse, trans, det, re, im, arg, exp, ln, sqrt, sin and cos
+    pub fn standard() -> Self {
+        let mut functions = Functions::new();
+        let real = |x: f64| Ok(Value::Complex(complex!(x, 0)));
+        let square = |m: &Matrix| {
+            if m.height != m.width {
+                return Err(MatrixError::NotSquare { shape: m.shape() }.to_string());
+            }
+            Ok(())
+        };
+        for name in ["conj", "conjugate"] {
+            functions.insert_unary(name, |t| {matches!(t, Type::Complex | Type::Quaternion).then_some(t)}, |x| match x {
+                Value::Complex(z) => Ok(Value::Complex(z.conjugate())),
+                Value::Quaternion(q) => Ok(Value::Quaternion(q.conjugate())),
+                _ => unreachable!()
+            });
+        }
+        for name in ["norm", "abs"] {
+            functions.insert_unary(name, |_| Some(Type::Complex), move |x| match x {
+                Value::Complex(z) => real(z.abs()),
+                Value::Quaternion(q) => real(q.norm()),
+                //frobenius norm
+                Value::Matrix(m) => real(m.as_slice().iter().map(|x| x*x).sum::<f64>().sqrt())
+            });
+        }
+        for name in ["inverse", "inv"] {
+            functions.insert_unary(name, Some, move |x| match x {
+                Value::Complex(z) =>
+                    z.checked_inverse().map(Value::Complex).ok_or("zero has no inverse".to_string()),
+                Value::Quaternion(q) =>
+                    q.checked_inverse().map(Value::Quaternion).ok_or("the zero quaternion has no inverse".to_string()),
+                Value::Matrix(m) => {
+                    square(m)?;
+                    m.inverse().map(Value::Matrix).ok_or(MatrixError::Singular.to_string())
+                }
+            });
+        }
+        for name in ["trans", "transpose"] {
+            functions.insert_unary(name, |t| {(t == Type::Matrix).then_some(t)}, |x| match x {
+                Value::Matrix(m) => Ok(Value::Matrix(m.trans())),
+                _ => unreachable!()
+            });
+        }
+        functions.insert_unary("det", |t| {(t == Type::Matrix).then_some(Type::Complex)}, move |x| match x {
+            Value::Matrix(m) => real(m.det().map_err(|e| e.to_string())?),
+            _ => unreachable!()
+        });
+        functions.insert_unary("re", |t| {(t != Type::Matrix).then_some(Type::Complex)}, move |x| match x {
+            Value::Complex(z) => real(z.Re),
+            Value::Quaternion(q) => real(q.Re),
+            _ => unreachable!()
+        });
+        functions.insert_unary("im", |t| {(t == Type::Complex).then_some(t)}, move |x| match x {
+            Value::Complex(z) => real(z.Im),
+            _ => unreachable!()
+        });
+        functions.insert_unary("arg", |t| {(t == Type::Complex).then_some(t)}, move |x| match x {
+            Value::Complex(z) => real(z.arg()),
+            _ => unreachable!()
+        });
+        functions.insert_unary("exp", |t| {(t != Type::Matrix).then_some(t)}, |x| match x {
+            Value::Complex(z) => Ok(Value::Complex(z.exp())),
+            Value::Quaternion(q) => Ok(Value::Quaternion(q.exp())),
+            _ => unreachable!()
+        });
+        functions.insert_unary("ln", |t| {(t != Type::Matrix).then_some(t)}, |x| match x {
+            Value::Complex(z) => Ok(Value::Complex(z.ln())),
+            Value::Quaternion(q) => Ok(Value::Quaternion(q.ln())),
+            _ => unreachable!()
+        });
+        functions.insert_unary("sqrt", |t| {(t != Type::Matrix).then_some(t)}, |x| match x {
+            Value::Complex(z) => Ok(Value::Complex(z.sqrt())),
+            Value::Quaternion(q) => Ok(Value::Quaternion(q.powf(0.5))),
+            _ => unreachable!()
+        });
+        functions.insert_unary("sin", |t| {(t == Type::Complex).then_some(t)}, |x| match x {
+            Value::Complex(z) => Ok(Value::Complex(z.sin())),
+            _ => unreachable!()
+        });
+        functions.insert_unary("cos", |t| {(t == Type::Complex).then_some(t)}, |x| match x {
+            Value::Complex(z) => Ok(Value::Complex(z.cos())),
+            _ => unreachable!()
+        });
+        functions
+    }
+
+    pub fn insert(&mut self, name: &str, function: Function) {
+        self.table.insert(name.to_string(), function);
+    }
+
+    //call only ever sees an argument that typing accepted
+    pub fn insert_unary(&mut self, name: &str, typing: impl Fn(Type) -> Option<Type> + 'static,
+                        call: impl Fn(&Value) -> std::result::Result<Value, String> + 'static) {
+        self.insert(name, Function {
+            arity: 1,
+            typing: Box::new(move |args| typing(args[0])),
+            call: Box::new(move |args| call(&args[0]))
+        });
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Function> {
+        self.table.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Function> {
+        self.table.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.table.contains_key(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.table.keys().map(|name| name.as_str()).collect();
+        names.sort();
+        names
+    }
+}
+//FUNCTIONS END
+
+//TOKENIZER START
+pub enum Token {
+    Number(f64),
+    //a number glued to i, j or k, like 4i
+    Imaginary(f64, char),
+    Name(String),
+    Symbol(char)
+}
+
+pub fn tokenize(s: &str) -> std::result::Result<Vec<(usize, Token)>, ExprError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let start = pos;
+        let c = chars[pos];
+        if c.is_whitespace() {
+            pos += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            while pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+                pos += 1;
+            }
+            if pos < chars.len() && (chars[pos] == 'e' || chars[pos] == 'E') {
+                let exponent = if pos + 1 < chars.len() && (chars[pos + 1] == '+' || chars[pos + 1] == '-') {pos + 2} else {pos + 1};
+                if exponent < chars.len() && chars[exponent].is_ascii_digit() {
+                    pos = exponent;
+                    while pos < chars.len() && chars[pos].is_ascii_digit() {
+                        pos += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..pos].iter().collect();
+            let value = match text.parse::<f64>() {
+                Ok(x) => x,
+                Err(_) => return Err(ExprError::InvalidNumber { position: start, text })
+            };
+            let glued = pos < chars.len() && matches!(chars[pos], 'i' | 'j' | 'k')
+                && !chars.get(pos + 1).is_some_and(|x| x.is_alphanumeric() || *x == '_');
+            if glued {
+                tokens.push((start, Token::Imaginary(value, chars[pos])));
+                pos += 1;
+            } else {
+                tokens.push((start, Token::Number(value)));
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while pos < chars.len() && (chars[pos].is_alphanumeric() || chars[pos] == '_') {
+                pos += 1;
+            }
+            tokens.push((start, Token::Name(chars[start..pos].iter().collect())));
+        } else if "+-*/^()[],".contains(c) {
+            tokens.push((start, Token::Symbol(c)));
+            pos += 1;
+        } else {
+            return Err(ExprError::UnexpectedChar { position: start, found: c });
+        }
+    }
+    Ok(tokens)
+}
+//TOKENIZER END
+
+//SYNTAX TREE START
+pub enum Expr {
+    Literal(Value),
+    Variable { name: String, position: usize },
+    Neg(Box<Expr>),
+    //op is one of + - * / ^
+    Binary { op: char, lhs: Box<Expr>, rhs: Box<Expr>, position: usize },
+    Call { name: String, args: Vec<Expr>, position: usize },
+    Matrix { rows: Vec<Vec<(Expr, usize)>>, position: usize }
+}
+
+//i, j, k, pi and e, unless a variable of the same name shadows them
+pub fn constant(name: &str) -> Option<Value> {
+    match name {
+        "i" => Some(Value::Complex(ComplexNumber::i())),
+        "j" => Some(Value::Quaternion(Quaternion::<f64>::newj())),
+        "k" => Some(Value::Quaternion(Quaternion::<f64>::newk())),
+        "pi" => Some(Value::Complex(complex!(std::f64::consts::PI, 0))),
+        "e" => Some(Value::Complex(complex!(std::f64::consts::E, 0))),
+        _ => None
+    }
+}
+
+fn lookup(name: &str, position: usize, vars: &HashMap<String, Value>) -> std::result::Result<Value, ExprError> {
+    vars.get(name).cloned().or_else(|| constant(name))
+        .ok_or(ExprError::UnknownVariable { position, name: name.to_string() })
+}
+
+//the result type of a binary operator, None if it is not defined for the operands
+fn binary_type(op: char, left: Type, right: Type) -> Option<Type> {
+    match (op, left, right) {
+        (_, Type::Complex, Type::Complex) => Some(Type::Complex),
+        ('^', Type::Matrix, Type::Complex) => Some(Type::Matrix),
+        (_, Type::Matrix, _) | (_, _, Type::Matrix) => match (op, left, right) {
+            ('+' | '-' | '*', Type::Matrix, Type::Matrix) => Some(Type::Matrix),
+            //matrices hold reals, so the scalar is checked again when evaluating
+            ('*' | '/', Type::Matrix, Type::Complex) | ('*', Type::Complex, Type::Matrix) => Some(Type::Matrix),
+            _ => None
+        },
+        _ => Some(Type::Quaternion)
+    }
+}
+
+fn binary(op: char, a: Value, b: Value, position: usize) -> std::result::Result<Value, ExprError> {
+    let mismatch = |a: &Value, b: &Value| ExprError::TypeMismatch { position, op, left: a.type_of(), right: b.type_of() };
+    let matrix_error = |error| ExprError::Matrix { position, error };
+    let not_real = |z: &ComplexNumber| ExprError::Domain { position, message: format!("matrices hold real entries, cannot scale by {}", z) };
+    if op == '^' {
+        return power(a, b, position);
+    }
+    //zero has no inverse in any scalar type, x / 0 would otherwise give inf and NaN parts
+    if op == '/' && b.to_quaternion().is_some_and(|q| q == Quaternion::zero()) {
+        return Err(ExprError::Domain { position, message: "division by zero".to_string() });
+    }
+    match (&a, &b) {
+        (Value::Complex(x), Value::Complex(y)) => Ok(Value::Complex(match op {
+            '+' => *x + *y,
+            '-' => *x - *y,
+            '*' => *x * *y,
+            _ => *x / *y
+        })),
+        (Value::Matrix(x), Value::Matrix(y)) => match op {
+            '+' => x.checked_add(y).map(Value::Matrix).map_err(matrix_error),
+            '-' => x.checked_sub(y).map(Value::Matrix).map_err(matrix_error),
+            '*' => x.checked_mul(y).map(Value::Matrix).map_err(matrix_error),
+            _ => Err(mismatch(&a, &b))
+        },
+        (Value::Matrix(x), Value::Complex(z)) => match (op, b.real()) {
+            ('*', Some(s)) => Ok(Value::Matrix(x.clone() * s)),
+            ('/', Some(s)) => Ok(Value::Matrix(x.clone() / s)),
+            ('*' | '/', None) => Err(not_real(z)),
+            _ => Err(mismatch(&a, &b))
+        },
+        (Value::Complex(z), Value::Matrix(y)) => match (op, a.real()) {
+            ('*', Some(s)) => Ok(Value::Matrix(s * y.clone())),
+            ('*', None) => Err(not_real(z)),
+            _ => Err(mismatch(&a, &b))
+        },
+        (Value::Matrix(_), _) | (_, Value::Matrix(_)) => Err(mismatch(&a, &b)),
+        _ => {
+            //mixed complex and quaternion operands, i is shared by both
+            let (x, y) = (a.to_quaternion().unwrap(), b.to_quaternion().unwrap());
+            Ok(Value::Quaternion(match op {
+                '+' => x + y,
+                '-' => x - y,
+                '*' => x * y,
+                //right division, x * y^-1
+                _ => x / y
+            }))
+        }
+    }
+}
+
+fn power(base: Value, exponent: Value, position: usize) -> std::result::Result<Value, ExprError> {
+    let integer = exponent.real().filter(|n| n.fract() == 0.0 && n.abs() <= i32::MAX as f64).map(|n| n as i32);
+    match (&base, &exponent) {
+        (Value::Complex(z), Value::Complex(w)) => Ok(Value::Complex(match integer {
+            Some(n) => z.powi(n),
+            None => z.powc(*w)
+        })),
+        (Value::Matrix(m), Value::Complex(_)) => {
+            let n = integer.ok_or(ExprError::Domain { position, message: "matrices can only be raised to integer powers".to_string() })?;
+            if m.height != m.width {
+                return Err(ExprError::Matrix { position, error: MatrixError::NotSquare { shape: m.shape() } });
+            }
+            let m = if n < 0 {m.inverse().ok_or(ExprError::Matrix { position, error: MatrixError::Singular })?} else {m.clone()};
+            m.pow(n.unsigned_abs()).map(Value::Matrix).map_err(|error| ExprError::Matrix { position, error })
+        },
+        (Value::Matrix(_), _) | (_, Value::Matrix(_)) =>
+            Err(ExprError::TypeMismatch { position, op: '^', left: base.type_of(), right: exponent.type_of() }),
+        _ => {
+            let q = base.to_quaternion().unwrap();
+            Ok(Value::Quaternion(match exponent.real() {
+                Some(x) => q.powf(x),
+                None => q.pow(exponent.to_quaternion().unwrap())
+            }))
+        }
+    }
+}
+
+impl Expr {
+    //the type the expression evaluates to, or the first operation that does not fit its operands
+    pub fn check(&self, vars: &HashMap<String, Value>, functions: &Functions) -> std::result::Result<Type, ExprError> {
+        match self {
+            Expr::Literal(value) => Ok(value.type_of()),
+            Expr::Variable { name, position } => lookup(name, *position, vars).map(|value| value.type_of()),
+            Expr::Neg(inner) => inner.check(vars, functions),
+            Expr::Binary { op, lhs, rhs, position } => {
+                let (left, right) = (lhs.check(vars, functions)?, rhs.check(vars, functions)?);
+                binary_type(*op, left, right).ok_or(ExprError::TypeMismatch { position: *position, op: *op, left, right })
+            },
+            Expr::Call { name, args, position } => {
+                let function = functions.get(name).ok_or(ExprError::UnknownFunction { position: *position, name: name.clone() })?;
+                if args.len() != function.arity {
+                    return Err(ExprError::ArgumentCount { position: *position, name: name.clone(), expected: function.arity, found: args.len() });
+                }
+                let types = args.iter().map(|arg| arg.check(vars, functions)).collect::<std::result::Result<Vec<Type>, ExprError>>()?;
+                (function.typing)(&types).ok_or(ExprError::ArgumentType { position: *position, name: name.clone(), found: types })
+            },
+            Expr::Matrix { rows, .. } => {
+                //whether a scalar entry is real is only known once it is evaluated, like j*j
+                for (entry, position) in rows.iter().flatten() {
+                    let found = entry.check(vars, functions)?;
+                    if found == Type::Matrix {
+                        return Err(ExprError::MatrixEntry { position: *position, found });
+                    }
+                }
+                Ok(Type::Matrix)
+            }
+        }
+    }
+
+    //evaluates without checking first, so type errors surface where they happen
+    pub fn eval(&self, vars: &HashMap<String, Value>, functions: &Functions) -> std::result::Result<Value, ExprError> {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Variable { name, position } => lookup(name, *position, vars),
+            //subtract from zero rather than negate, so -4 has a +0 imaginary part and sqrt(-4) is 2i
+            Expr::Neg(inner) => Ok(match inner.eval(vars, functions)? {
+                Value::Complex(z) => Value::Complex(ComplexNumber::new() - z),
+                Value::Quaternion(q) => Value::Quaternion(Quaternion::zero() - q),
+                Value::Matrix(m) => Value::Matrix(m.apply(|x| 0.0 - x))
+            }),
+            Expr::Binary { op, lhs, rhs, position } => binary(*op, lhs.eval(vars, functions)?, rhs.eval(vars, functions)?, *position),
+            Expr::Call { name, args, position } => {
+                let function = functions.get(name).ok_or(ExprError::UnknownFunction { position: *position, name: name.clone() })?;
+                if args.len() != function.arity {
+                    return Err(ExprError::ArgumentCount { position: *position, name: name.clone(), expected: function.arity, found: args.len() });
+                }
+                let values = args.iter().map(|arg| arg.eval(vars, functions)).collect::<std::result::Result<Vec<Value>, ExprError>>()?;
+                let types: Vec<Type> = values.iter().map(|value| value.type_of()).collect();
+                if (function.typing)(&types).is_none() {
+                    return Err(ExprError::ArgumentType { position: *position, name: name.clone(), found: types });
+                }
+                (function.call)(&values).map_err(|message| ExprError::Domain { position: *position, message })
+            },
+            Expr::Matrix { rows, position } => {
+                let mut entries = Vec::with_capacity(rows.len());
+                for row in rows {
+                    let mut entry_row = Vec::with_capacity(row.len());
+                    for (entry, at) in row {
+                        let value = entry.eval(vars, functions)?;
+                        match value.real() {
+                            Some(x) => entry_row.push(x),
+                            None => return Err(ExprError::MatrixEntry { position: *at, found: value.type_of() })
+                        }
+                    }
+                    entries.push(entry_row);
+                }
+                Matrix::from_rows(&entries).map(Value::Matrix).map_err(|error| ExprError::Matrix { position: *position, error })
+            }
+        }
+    }
+}
+//SYNTAX TREE END
+
+//PARSER START
+//recursive descent over the tokens:
+//  sum     = product (('+' | '-') product)*
+//  product = unary (('*' | '/') unary)*
+//  unary   = '-' unary | power
+//  power   = atom ('^' unary)?
+//  atom    = number | name | name '(' args ')' | '(' sum ')' | '[' rows ']'
+pub struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize
+}
+
+//parses a whole string into a syntax tree
+pub fn parse(s: &str) -> std::result::Result<Expr, ExprError> {
+    let mut parser = Parser::new(s)?;
+    let expr = parser.sum()?;
+    parser.finish()?;
+    Ok(expr)
+}
+
+//parses, type checks and evaluates
+pub fn evaluate(s: &str, vars: &HashMap<String, Value>, functions: &Functions) -> std::result::Result<Value, ExprError> {
+    let expr = parse(s)?;
+    expr.check(vars, functions)?;
+    expr.eval(vars, functions)
+}
+
+impl Parser {
+    pub fn new(s: &str) -> std::result::Result<Self, ExprError> {
+        Ok(Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+            end: s.chars().count()
+        })
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |t| t.0)
+    }
+
+    fn peek(&self) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some((_, Token::Symbol(c))) => Some(*c),
+            _ => None
+        }
+    }
+
+    fn unexpected(&self) -> ExprError {
+        let (position, found) = match self.tokens.get(self.pos) {
+            Some((position, Token::Symbol(c))) => return ExprError::UnexpectedChar { position: *position, found: *c },
+            Some((position, Token::Number(x))) => (position, x.to_string()),
+            Some((position, Token::Imaginary(x, unit))) => (position, format!("{}{}", x, unit)),
+            Some((position, Token::Name(name))) => (position, name.clone()),
+            None => return ExprError::UnexpectedEnd { position: self.end }
+        };
+        ExprError::UnexpectedToken { position: *position, found }
+    }
+
+    fn expect(&mut self, symbol: char) -> std::result::Result<(), ExprError> {
+        if self.peek() != Some(symbol) {
+            return Err(self.unexpected());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    //fails unless every token was used
+    pub fn finish(&self) -> std::result::Result<(), ExprError> {
+        if self.pos < self.tokens.len() {
+            return Err(self.unexpected());
+        }
+        Ok(())
+    }
+
+    pub fn sum(&mut self) -> std::result::Result<Expr, ExprError> {
+        let mut expr = self.product()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            let position = self.position();
+            self.pos += 1;
+            let rhs = self.product()?;
+            expr = Expr::Binary { op, lhs: Box::new(expr), rhs: Box::new(rhs), position };
+        }
+        Ok(expr)
+    }
+
+    fn product(&mut self) -> std::result::Result<Expr, ExprError> {
+        let mut expr = self.unary()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            let position = self.position();
+            self.pos += 1;
+            let rhs = self.unary()?;
+            expr = Expr::Binary { op, lhs: Box::new(expr), rhs: Box::new(rhs), position };
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> std::result::Result<Expr, ExprError> {
+        if self.peek() == Some('-') {
+            self.pos += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> std::result::Result<Expr, ExprError> {
+        let base = self.atom()?;
+        if self.peek() == Some('^') {
+            let position = self.position();
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(Expr::Binary { op: '^', lhs: Box::new(base), rhs: Box::new(exponent), position });
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> std::result::Result<Expr, ExprError> {
+        let position = self.position();
+        let expr = match self.tokens.get(self.pos) {
+            Some((_, Token::Number(x))) => Expr::Literal(Value::Complex(complex!(*x, 0))),
+            Some((_, Token::Imaginary(x, unit))) => Expr::Literal(match unit {
+                'i' => Value::Complex(complex!(0, *x)),
+                'j' => Value::Quaternion(quaternion!(0, 0, *x, 0)),
+                _ => Value::Quaternion(quaternion!(0, 0, 0, *x))
+            }),
+            Some((_, Token::Name(name))) => {
+                let name = name.clone();
+                self.pos += 1;
+                if self.peek() != Some('(') {
+                    return Ok(Expr::Variable { name, position });
+                }
+                self.pos += 1;
+                let mut args = Vec::new();
+                if self.peek() != Some(')') {
+                    args.push(self.sum()?);
+                    while self.peek() == Some(',') {
+                        self.pos += 1;
+                        args.push(self.sum()?);
+                    }
+                }
+                self.expect(')')?;
+                return Ok(Expr::Call { name, args, position });
+            },
+            Some((_, Token::Symbol('('))) => {
+                self.pos += 1;
+                let expr = self.sum()?;
+                self.expect(')')?;
+                return Ok(expr);
+            },
+            Some((_, Token::Symbol('['))) => return self.matrix(),
+            _ => return Err(self.unexpected())
+        };
+        self.pos += 1;
+        Ok(expr)
+    }
+
+    //[[1, 2], [3, 4]], or [1, 2] for a single row
+    fn matrix(&mut self) -> std::result::Result<Expr, ExprError> {
+        let position = self.position();
+        self.expect('[')?;
+        let mut rows = Vec::new();
+        if self.peek() == Some('[') {
+            rows.push(self.matrix_row()?);
+            while self.peek() == Some(',') {
+                self.pos += 1;
+                rows.push(self.matrix_row()?);
+            }
+            self.expect(']')?;
+        } else {
+            self.pos -= 1;
+            rows.push(self.matrix_row()?);
+        }
+        Ok(Expr::Matrix { rows, position })
+    }
+
+    fn matrix_row(&mut self) -> std::result::Result<Vec<(Expr, usize)>, ExprError> {
+        self.expect('[')?;
+        let mut row = Vec::new();
+        loop {
+            let position = self.position();
+            row.push((self.sum()?, position));
+            if self.peek() != Some(',') {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.expect(']')?;
+        Ok(row)
+    }
+}
+//PARSER END
+
+//SESSION START
+//matrices start on their own line so the rows stay aligned
+pub fn assignment(name: &str, value: &Value) -> String {
+    match value {
+        Value::Matrix(_) => format!("{} =\n{}", name, value),
+        _ => format!("{} = {}", name, value)
+    }
+}
+
+//the variables and history of the calculator, results come back as lines of text
+pub struct Session {
+    pub vars: HashMap<String, Value>,
+    pub functions: Functions,
+    pub history: Vec<String>
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Session {
+            vars: HashMap::new(),
+            functions: Functions::standard(),
+            history: Vec::new()
+        }
+    }
+
+    //runs one line of ;-separated statements, one output line per result,
+    //stopping at the first error
+    pub fn run(&mut self, line: &str) -> Vec<String> {
+        let mut output = Vec::new();
+        let mut offset = 0;
+        for statement in line.split(';') {
+            let start = offset;
+            offset += statement.chars().count() + 1;
+            if statement.trim().is_empty() {
+                continue;
+            }
+            match self.statement(statement, start) {
+                Ok(text) => output.push(text),
+                Err(e) => {
+                    output.push(format!("error: {}", e));
+                    break;
+                }
+            }
+        }
+        output
+    }
+
+    pub fn statement(&mut self, statement: &str, offset: usize) -> std::result::Result<String, String> {
+        //name = expr, but not name == or a bare expression
+        let target = statement.split_once('=').map(|(lhs, rhs)| (lhs.trim(), rhs)).filter(|(lhs, rhs)| {
+            !lhs.is_empty() && lhs.chars().all(|c| c.is_alphanumeric() || c == '_')
+                && !lhs.starts_with(|c: char| c.is_ascii_digit()) && !rhs.starts_with('=')
+        });
+        let (name, expression, offset) = match target {
+            Some((name, rhs)) => (Some(name.to_string()), rhs, offset + statement.chars().count() - rhs.chars().count()),
+            None => (None, statement, offset)
+        };
+        if name.as_deref().is_some_and(|n| self.functions.contains(n)) {
+            return Err(format!("cannot assign to the function {}", name.unwrap()));
+        }
+        //pad to where the expression starts, so error positions count from the start of the line
+        let padded = format!("{}{}", " ".repeat(offset), expression);
+        let value = evaluate(&padded, &self.vars, &self.functions).map_err(|e| e.to_string())?;
+        let text = match &name {
+            Some(name) => assignment(name, &value),
+            None => value.to_string()
+        };
+        if let Some(name) = name {
+            self.vars.insert(name, value.clone());
+        }
+        self.vars.insert("ans".to_string(), value);
+        Ok(text)
+    }
+
+    //one assignment per variable, sorted by name
+    pub fn variables(&self) -> Vec<String> {
+        let mut names: Vec<&String> = self.vars.keys().collect();
+        names.sort();
+        names.into_iter().map(|name| assignment(name, &self.vars[name])).collect()
+    }
+
+    pub fn help(&self) -> Vec<String> {
+        vec![
+            "expressions use + - * / ^, parentheses and the units i, j, k, for example (3+4i)*(1-2i)".to_string(),
+            "matrices are written [[1, 2], [3, 4]] and hold real entries".to_string(),
+            "name = expr assigns a variable, ; separates statements and ans holds the last result".to_string(),
+            format!("functions: {}", self.functions.names().join(", ")),
+            "commands: help, vars, history, !n to rerun history entry n, quit".to_string()
+        ]
+    }
+}
+//SESSION END
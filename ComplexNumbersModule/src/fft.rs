@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::complex::*;
+
+//FFT START
+//forward transforms use exp(-2 pi i jk / n) and are unscaled, inverse transforms scale by 1 / n
+enum Algorithm {
+    //lengths 0 and 1 are their own transform
+    Trivial,
+    //in-place cooley-tukey, twiddles holds exp(-2 pi i k / n) for k < n / 2
+    Radix2 { twiddles: Vec<ComplexNumber> },
+    //length n as a circular convolution of power of two length, chirp holds exp(-pi i k^2 / n)
+    //and kernel the transform of the conjugate chirp laid out for that convolution
+    Bluestein { chirp: Vec<ComplexNumber>, kernel: Vec<ComplexNumber>, inner: Arc<Fft> }
+}
+
+pub struct Fft {
+    len: usize,
+    algorithm: Algorithm
+}
+
+//exp(-2 pi i k / n)
+fn twiddle(k: usize, n: usize) -> ComplexNumber {
+    let theta = -2.0 * std::f64::consts::PI * k as f64 / n as f64;
+    complex!(theta.cos(), theta.sin())
+}
+
+fn radix2(data: &mut [ComplexNumber], twiddles: &[ComplexNumber]) {
+    let n = data.len();
+    let bits = n.trailing_zeros();
+    for x in 0..n {
+        let y = x.reverse_bits() >> (usize::BITS - bits);
+        if x < y {
+            data.swap(x, y);
+        }
+    }
+    let mut size = 2;
+    while size <= n {
+        let (half, step) = (size / 2, n / size);
+        for block in data.chunks_mut(size) {
+            let (low, high) = block.split_at_mut(half);
+            for k in 0..half {
+                let t = high[k] * twiddles[k * step];
+                high[k] = low[k] - t;
+                low[k] += t;
+            }
+        }
+        size *= 2;
+    }
+}
+
+impl Fft {
+    pub fn new(len: usize) -> Self {
+        let inner = Fft::bluestein_len(len).map(|m| Arc::new(Fft::new(m)));
+        Fft::build(len, inner)
+    }
+
+    //the power of two length a bluestein transform of len convolves over, None if len needs no convolution
+    fn bluestein_len(len: usize) -> Option<usize> {
+        if len < 2 || len.is_power_of_two() {
+            None
+        } else {
+            Some((2 * len - 1).next_power_of_two())
+        }
+    }
+
+    fn build(len: usize, inner: Option<Arc<Fft>>) -> Self {
+        let algorithm = match inner {
+            _ if len < 2 => Algorithm::Trivial,
+            None => Algorithm::Radix2 {
+                twiddles: (0..len / 2).map(|k| twiddle(k, len)).collect()
+            },
+            Some(inner) => {
+                //k^2 taken mod 2n keeps the angle small, exp(-pi i k^2 / n) has period 2n in k^2
+                let chirp: Vec<ComplexNumber> = (0..len).map(|k| twiddle((k * k) % (2 * len), 2 * len)).collect();
+                let m = inner.len;
+                let mut kernel = vec![ComplexNumber::new(); m];
+                kernel[0] = chirp[0].conjugate();
+                for k in 1..len {
+                    kernel[k] = chirp[k].conjugate();
+                    kernel[m - k] = chirp[k].conjugate();
+                }
+                inner.process(&mut kernel);
+                Algorithm::Bluestein { chirp, kernel, inner }
+            }
+        };
+        Fft { len, algorithm }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    //forward transform in place
+    pub fn process(&self, data: &mut [ComplexNumber]) {
+        assert!(data.len() == self.len, "fft planned for {} samples applied to {}", self.len, data.len());
+        match &self.algorithm {
+            Algorithm::Trivial => (),
+            Algorithm::Radix2 { twiddles } => radix2(data, twiddles),
+            Algorithm::Bluestein { chirp, kernel, inner } => {
+                let mut buffer = vec![ComplexNumber::new(); inner.len];
+                for k in 0..self.len {
+                    buffer[k] = data[k] * chirp[k];
+                }
+                inner.process(&mut buffer);
+                for k in 0..inner.len {
+                    buffer[k] *= kernel[k];
+                }
+                inner.process_inverse(&mut buffer);
+                for k in 0..self.len {
+                    data[k] = buffer[k] * chirp[k];
+                }
+            }
+        }
+    }
+
+    //inverse transform in place, scaled so it undoes process
+    pub fn process_inverse(&self, data: &mut [ComplexNumber]) {
+        //conj(fft(conj(x))) is n times the inverse
+        data.iter_mut().for_each(|x| *x = x.conjugate());
+        self.process(data);
+        let scale = 1.0 / self.len as f64;
+        data.iter_mut().for_each(|x| *x = x.conjugate() * scale);
+    }
+}
+
+//transform of real input, only the n / 2 + 1 non-redundant bins are returned since
+//the rest are their conjugates
+pub struct RealFft {
+    len: usize,
+    //for even lengths, the samples are packed in pairs into a complex transform of half the length
+    packed: bool,
+    inner: Arc<Fft>,
+    twiddles: Vec<ComplexNumber>
+}
+
+impl RealFft {
+    pub fn new(len: usize) -> Self {
+        RealFft::build(len, Arc::new(Fft::new(RealFft::inner_len(len))))
+    }
+
+    //length of the complex transform behind a real one
+    #[allow(clippy::manual_is_multiple_of)]
+    fn inner_len(len: usize) -> usize {
+        if len >= 2 && len % 2 == 0 {len / 2} else {len}
+    }
+
+    fn build(len: usize, inner: Arc<Fft>) -> Self {
+        let packed = RealFft::inner_len(len) != len;
+        RealFft {
+            len,
+            packed,
+            inner,
+            twiddles: if packed {(0..=len / 2).map(|k| twiddle(k, len)).collect()} else {Vec::new()}
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn process(&self, input: &[f64]) -> Vec<ComplexNumber> {
+        assert!(input.len() == self.len, "fft planned for {} samples applied to {}", self.len, input.len());
+        if !self.packed {
+            let mut data: Vec<ComplexNumber> = input.iter().map(|&x| complex!(x, 0)).collect();
+            self.inner.process(&mut data);
+            data.truncate(self.len / 2 + 1);
+            return data;
+        }
+        let half = self.len / 2;
+        let mut z: Vec<ComplexNumber> = input.chunks(2).map(|pair| complex!(pair[0], pair[1])).collect();
+        self.inner.process(&mut z);
+        //split the packed transform into the transforms of the even and odd samples
+        (0..=half).map(|k| {
+            let (a, b) = (z[k % half], z[(half - k) % half].conjugate());
+            let even = (a + b) * 0.5;
+            let odd = (a - b) * complex!(0, -0.5);
+            even + self.twiddles[k] * odd
+        }).collect()
+    }
+}
+
+//hands out transforms for any length, reusing twiddle factors and chirps across calls
+pub struct FftPlanner {
+    plans: HashMap<usize, Arc<Fft>>,
+    real_plans: HashMap<usize, Arc<RealFft>>
+}
+
+impl Default for FftPlanner {
+    fn default() -> Self {
+        FftPlanner::new()
+    }
+}
+
+impl FftPlanner {
+    pub fn new() -> Self {
+        FftPlanner {
+            plans: HashMap::new(),
+            real_plans: HashMap::new()
+        }
+    }
+
+    pub fn plan(&mut self, len: usize) -> Arc<Fft> {
+        if let Some(plan) = self.plans.get(&len) {
+            return plan.clone();
+        }
+        let inner = Fft::bluestein_len(len).map(|m| self.plan(m));
+        let plan = Arc::new(Fft::build(len, inner));
+        self.plans.insert(len, plan.clone());
+        plan
+    }
+
+    pub fn plan_real(&mut self, len: usize) -> Arc<RealFft> {
+        if let Some(plan) = self.real_plans.get(&len) {
+            return plan.clone();
+        }
+        let inner = self.plan(RealFft::inner_len(len));
+        let plan = Arc::new(RealFft::build(len, inner));
+        self.real_plans.insert(len, plan.clone());
+        plan
+    }
+}
+
+//one-off transforms, use an FftPlanner to reuse the setup over many calls of the same length
+pub fn fft(data: &mut [ComplexNumber]) {
+    Fft::new(data.len()).process(data);
+}
+
+pub fn ifft(data: &mut [ComplexNumber]) {
+    Fft::new(data.len()).process_inverse(data);
+}
+
+pub fn rfft(input: &[f64]) -> Vec<ComplexNumber> {
+    RealFft::new(input.len()).process(input)
+}
+//FFT END
@@ -0,0 +1,22 @@
+#![allow(non_snake_case)]
+//complex numbers, quaternions and matrices over them
+
+#[macro_use]
+pub mod traits;
+pub mod parse;
+#[macro_use]
+pub mod complex;
+#[macro_use]
+pub mod quaternion;
+#[macro_use]
+pub mod matrix;
+pub mod expr;
+pub mod fft;
+pub mod polynomial;
+
+pub use traits::{Vectorable, Ring, DivisionRing, Field, Real, ComplexField};
+pub use parse::ParseNumberError;
+pub use complex::{Complex, ComplexNumber, Polar};
+pub use quaternion::{Quaternion, EulerAxes, EulerSequence};
+pub use matrix::{Matrice, Matrix, MatrixError, Lu, Qr, Svd, Eigen, SymmetricEigen};
+pub use polynomial::Polynomial;
@@ -0,0 +1,1461 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use core::ops::Add;
+use core::ops::Sub;
+use std::ops::Mul;
+use std::ops::Div;
+use std::fmt::Debug;
+use std::ops::Index;
+use std::ops::IndexMut;
+use crate::traits::*;
+use crate::complex::*;
+use crate::quaternion::Quaternion;
+
+//MATRIX START
+pub struct Matrice<T> {
+    pub height: usize,
+    pub width: usize,
+    //row-major, entry (x, y) lives at x * width + y
+    pub data: Vec<T>
+}
+
+pub type Matrix = Matrice<f64>;
+
+pub enum MatrixError {
+    DimensionMismatch { left: (usize, usize), right: (usize, usize) },
+    NotSquare { shape: (usize, usize) },
+    Underdetermined { shape: (usize, usize) },
+    Singular,
+    RankDeficient,
+    NotPositiveDefinite,
+    NotSymmetric,
+    DataLength { expected: usize, found: usize },
+    RaggedRows { row: usize, expected: usize, found: usize }
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            MatrixError::DimensionMismatch { left, right } =>
+                write!(f, "dimension mismatch: {}x{} and {}x{}", left.0, left.1, right.0, right.1),
+            MatrixError::NotSquare { shape } =>
+                write!(f, "expected a square matrix, got {}x{}", shape.0, shape.1),
+            MatrixError::Underdetermined { shape } =>
+                write!(f, "expected at least as many rows as columns, got {}x{}", shape.0, shape.1),
+            MatrixError::Singular => write!(f, "matrix is singular"),
+            MatrixError::RankDeficient => write!(f, "matrix does not have full column rank"),
+            MatrixError::NotPositiveDefinite => write!(f, "matrix is not positive definite"),
+            MatrixError::NotSymmetric => write!(f, "matrix is not symmetric"),
+            MatrixError::DataLength { expected, found } =>
+                write!(f, "expected {} entries, got {}", expected, found),
+            MatrixError::RaggedRows { row, expected, found } =>
+                write!(f, "row {} has {} entries, expected {}", row, found, expected)
+        }
+    }
+}
+
+impl Debug for MatrixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+#[macro_export]
+macro_rules! matrix {
+
+    ($ex:expr, $ex2:expr) => {
+        $crate::matrix::Matrix {
+            height: $ex,
+            width: $ex2,
+            data: ::std::vec![0f64;$ex*$ex2]
+        }
+    };
+
+    ($ex:expr, $ex2:expr, $ex3:expr) => {
+        $crate::matrix::Matrix :: new($ex, $ex2, $ex3)
+    };
+
+}
+
+impl<T: Debug> Display for Matrice<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let mut temp: String = "".to_string();
+        for row in self.data.chunks(self.width.max(1)) {
+            temp = format!("{}{:?}\n" ,temp,row);
+        }
+        write!(f,"{}", temp)
+    }
+}
+
+impl<T: Ring> Add<Matrice<T>> for Matrice<T> {
+    type Output = Matrice<T>;
+    fn add(self, other: Matrice<T>) -> Matrice<T> {
+        match self.checked_add(&other) {
+            Ok(sum) => sum,
+            Err(e) => panic!("cannot add matrices: {}", e)
+        }
+    }
+} 
+
+impl<T: Ring> Sub<Matrice<T>> for Matrice<T> {
+    type Output = Matrice<T>;
+    fn sub(self, other: Matrice<T>) -> Matrice<T> {
+        match self.checked_sub(&other) {
+            Ok(difference) => difference,
+            Err(e) => panic!("cannot subtract matrices: {}", e)
+        }
+    }
+} 
+
+impl<T: Ring> Mul<Matrice<T>> for Matrice<T> {
+    type Output = Matrice<T>;
+    fn mul(self, other: Matrice<T>) -> Matrice<T> {
+        match self.checked_mul(&other) {
+            Ok(product) => product,
+            Err(e) => panic!("cannot multiply matrices: {}", e)
+        }
+    }
+} 
+
+impl<T: Ring> Mul<T> for Matrice<T> {
+    type Output = Matrice<T>;
+    fn mul(self, other: T) -> Matrice<T> {
+        self.apply(|x| {x*other})
+    }
+} 
+
+impl Mul<Matrix> for f64 {
+    type Output = Matrix;
+    fn mul(self, other:Matrix) -> Matrix {
+        other.apply(|x| {self*x})
+    }
+}
+
+impl Mul<Matrice<f32>> for f32 {
+    type Output = Matrice<f32>;
+    fn mul(self, other:Matrice<f32>) -> Matrice<f32> {
+        other.apply(|x| {self*x})
+    }
+}
+
+impl<T: Real> Mul<Matrice<Complex<T>>> for Complex<T> {
+    type Output = Matrice<Complex<T>>;
+    fn mul(self, other:Matrice<Complex<T>>) -> Matrice<Complex<T>> {
+        other.apply(|x| {self*x})
+    }
+}
+
+impl<T: Real> Mul<Matrice<Quaternion<T>>> for Quaternion<T> {
+    type Output = Matrice<Quaternion<T>>;
+    fn mul(self, other:Matrice<Quaternion<T>>) -> Matrice<Quaternion<T>> {
+        other.apply(|x| {self*x})
+    }
+}
+
+impl<T: DivisionRing> Div<T> for Matrice<T> {
+    type Output = Matrice<T>;
+    fn div(self, other: T) -> Matrice<T> {
+        self.apply(|x| {x.div_right(other)})
+    }
+}
+
+impl<T: Clone> Clone for Matrice<T> {
+    fn clone(&self) -> Self {
+        Self {
+            height: self.height,
+            width: self.width,
+            data: self.data.clone()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Matrice<T> {
+    fn eq(&self, other: &Matrice<T>) -> bool {
+        self.height == other.height && self.width == other.width && self.data == other.data
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrice<T> {
+    type Output = T;
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.height && y < self.width, "index ({}, {}) out of bounds for a {}x{} matrix", x, y, self.height, self.width);
+        &self.data[x * self.width + y]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrice<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.height && y < self.width, "index ({}, {}) out of bounds for a {}x{} matrix", x, y, self.height, self.width);
+        &mut self.data[x * self.width + y]
+    }
+}
+
+impl<T: Ring> Matrice<T> {
+    pub fn new(height: usize, width: usize, this: Vec<Vec<T>>) -> Self {
+        //rows and columns missing from this are filled with zeros
+        if this.len() > height || this.iter().any(|row| row.len() > width) {
+            panic!("{}x{} data does not fit in a {}x{} matrix", this.len(),
+                this.iter().map(|row| row.len()).max().unwrap_or(0), height, width);
+        }
+        let mut temp = Self::new_zero(height, width);
+        for (x, row) in this.iter().enumerate() {
+            temp.row_mut(x)[..row.len()].copy_from_slice(row);
+        }
+        temp
+    }
+
+    pub fn new_zero(height:usize, width:usize) -> Self {
+        Self {
+            height,
+            width,
+            data: vec![T::zero();height*width]
+        }
+    }
+
+    pub fn new_default(height:usize, width:usize) -> Self where T: Default {
+        Self {
+            height,
+            width,
+            data: vec![T::default();height*width]
+        }
+    }
+
+    pub fn from_vec(height: usize, width: usize, data: Vec<T>) -> std::result::Result<Self, MatrixError> {
+        //takes ownership of row-major data without copying
+        if data.len() != height * width {
+            return Err(MatrixError::DataLength { expected: height * width, found: data.len() });
+        }
+        Ok(Self {
+            height,
+            width,
+            data
+        })
+    }
+
+    pub fn from_rows(rows: &[Vec<T>]) -> std::result::Result<Self, MatrixError> {
+        //every row must have as many entries as the first
+        let width = rows.first().map_or(0, |row| row.len());
+        let mut data = Vec::with_capacity(rows.len() * width);
+        for (x, row) in rows.iter().enumerate() {
+            if row.len() != width {
+                return Err(MatrixError::RaggedRows { row: x, expected: width, found: row.len() });
+            }
+            data.extend_from_slice(row);
+        }
+        Self::from_vec(rows.len(), width, data)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    pub fn stride(&self) -> usize {
+        //distance in the buffer between vertically adjacent entries
+        self.width
+    }
+
+    pub fn row(&self, x: usize) -> &[T] {
+        &self.data[x * self.width..(x + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, x: usize) -> &mut [T] {
+        &mut self.data[x * self.width..(x + 1) * self.width]
+    }
+
+    pub fn column(&self, y: usize) -> Vec<T> {
+        //columns are strided, so this copies
+        self.data.iter().skip(y).step_by(self.width.max(1)).copied().collect()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |x| self.row(x))
+    }
+
+    pub fn rows_mut(&mut self) -> std::slice::ChunksMut<'_, T> {
+        self.data.chunks_mut(self.width.max(1))
+    }
+
+    pub fn swap_rows(&mut self, a: usize, b: usize) {
+        if a != b {
+            let (low, high) = (a.min(b), a.max(b));
+            let (top, bottom) = self.data.split_at_mut(high * self.width);
+            top[low * self.width..(low + 1) * self.width].swap_with_slice(&mut bottom[..self.width]);
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    pub fn minor(&self, i: usize, j: usize) -> Self {
+        let mut minor: Self = Self::new_zero(self.height-1,
+                                        self.width-1);
+        for x in (0..self.height).filter(|&x| x != i) {
+            for y in (0..self.width).filter(|&y| y != j) {
+                minor[(if x > i {x-1} else {x}, if y > j {y-1} else {y})] =
+                     self[(x, y)];
+            }
+        }
+        minor
+    }
+
+    pub fn trans(&self) -> Self {
+       let mut trans: Self = Self::new_zero(self.width,self.height);
+        for x in 0..self.height {
+            for y in 0..self.width {
+                trans[(y, x)] =
+                    self[(x, y)];
+            }
+        }
+        trans
+    }
+
+    pub fn apply(&self, f: impl Fn(T) -> T) -> Self {
+        let mut temp = Self::new_zero(self.height, self.width);
+        for x in 0..temp.height {
+            for y in 0..temp.width {
+                temp[(x, y)] = f(self[(x, y)]);
+            }
+        }
+        temp
+    }
+
+    pub fn replace_collumn_with(&self, collumn: usize, new_collumn: Vec<T>) -> Self {
+        let mut temp = self.clone();
+        for row in 0..min(temp.height as f64, new_collumn.len() as f64) {
+            temp[(row, collumn)] = new_collumn[row];
+        }
+        temp
+    }
+
+    pub fn replace_line_with(&self, line: usize, new_line: Vec<T>) -> Self {
+        let mut temp = self.clone();
+        for col in 0..min(temp.width as f64, new_line.len() as f64) {
+            temp[(line, col)] = new_line[col];
+        }
+        temp
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<T>> {
+        self.rows().map(|row| row.to_vec()).collect()
+    }
+
+    pub fn printm(&self) where T: Debug {
+        self.rows().for_each(|v|{println!("{:?}",v)});
+    }
+
+    pub fn identitymat(height: usize) -> Self {
+        let mut temp: Self = Self::new_zero(height, height);
+        for x in 0..height {
+            for y in 0..height {
+                if x == y {
+                    temp[(x, y)] = T::one();
+                }
+            }
+        }
+        temp
+    }
+
+    pub fn pow(&self, n: u32) -> std::result::Result<Self, MatrixError> {
+        //exponentiation by squaring, A^0 = I
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        let mut base = self.clone();
+        let mut exp = n;
+        let mut result = Self::identitymat(self.height);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result * base.clone();
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.clone() * base;
+            }
+        }
+        Ok(result)
+    }
+
+    pub fn checked_add(&self, other: &Matrice<T>) -> std::result::Result<Matrice<T>, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::DimensionMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut temp = Self::new_zero(self.height, self.width);
+        for x in 0..self.height {
+            for y in 0..self.width {
+                temp[(x, y)] = self[(x, y)] + other[(x, y)];
+            }
+        }
+        Ok(temp)
+    }
+
+    pub fn checked_sub(&self, other: &Matrice<T>) -> std::result::Result<Matrice<T>, MatrixError> {
+        if self.shape() != other.shape() {
+            return Err(MatrixError::DimensionMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut temp = Self::new_zero(self.height, self.width);
+        for x in 0..self.height {
+            for y in 0..self.width {
+                temp[(x, y)] = self[(x, y)] - other[(x, y)];
+            }
+        }
+        Ok(temp)
+    }
+
+    pub fn checked_mul(&self, other: &Matrice<T>) -> std::result::Result<Matrice<T>, MatrixError> {
+        //(m x n) * (n x p) = (m x p)
+        if self.width != other.height {
+            return Err(MatrixError::DimensionMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut temp = Self::new_zero(self.height, other.width);
+        for x in 0..self.height {
+            for k in 0..self.width {
+                let a = self[(x, k)];
+                for y in 0..other.width {
+                    temp[(x, y)] = temp[(x, y)] + a * other[(k, y)];
+                }
+            }
+        }
+        Ok(temp)
+    }
+}
+
+impl<T: DivisionRing> Matrice<T> {
+    pub fn det(&self) -> std::result::Result<T, MatrixError> {
+        Ok(self.lu()?.det())
+    }
+
+    pub fn adjoint(&self) -> Self {
+        //conjugate transpose
+        self.trans().apply(|x| {x.conj()})
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        let scale = self.data.iter().fold(0.0, |m: f64, x| m.max(x.abs().to_f64()));
+        self.height == self.width && (0..self.height).all(|x| (0..x).all(|y|
+            (self[(x, y)] - self[(y, x)]).abs().to_f64() <= self.height as f64 * epsilon::<T>() * scale))
+    }
+
+    pub fn lu(&self) -> std::result::Result<Lu<T>, MatrixError> {
+        //Doolittle elimination with partial pivoting, PA = LU
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        let n = self.height;
+        let mut a = self.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+        let scale = a.data.iter().fold(0.0, |m: f64, x| m.max(x.abs().to_f64()));
+        for k in 0..n {
+            let p = (k..n).fold(k, |p, i| if a[(i, k)].abs() > a[(p, k)].abs() {i} else {p});
+            if p != k {
+                a.swap_rows(p, k);
+                perm.swap(p, k);
+                sign = -sign;
+            }
+            let pivot = a[(k, k)];
+            if pivot == T::zero() {
+                //the whole column is zero below the diagonal, nothing to eliminate
+                continue;
+            }
+            for i in k+1..n {
+                a[(i, k)] = a[(i, k)].div_right(pivot);
+                for j in k+1..n {
+                    a[(i, j)] = a[(i, j)] - a[(i, k)] * a[(k, j)];
+                }
+            }
+        }
+        Ok(Lu {
+            lu: a,
+            perm,
+            sign,
+            tolerance: n as f64 * epsilon::<T>() * scale
+        })
+    }
+
+    pub fn inverse(&self) -> Option<Self>{
+        //None when the matrix is not square or is singular up to rounding
+        self.lu().ok()?.inverse()
+    }
+
+    pub fn solve(&self, b: &[T]) -> std::result::Result<Vec<T>, MatrixError> {
+        //x with self * x = b, for square self
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        if b.len() != self.height {
+            return Err(MatrixError::DimensionMismatch { left: (self.height, self.width), right: (b.len(), 1) });
+        }
+        let lu = self.lu()?;
+        if lu.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+        Ok(lu.solve_vec(b))
+    }
+
+    pub fn solve_matrix(&self, b: &Matrice<T>) -> std::result::Result<Matrice<T>, MatrixError> {
+        //X with self * X = b, one column of b per right hand side
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        if b.height != self.height {
+            return Err(MatrixError::DimensionMismatch { left: (self.height, self.width), right: (b.height, b.width) });
+        }
+        let lu = self.lu()?;
+        if lu.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+        let mut x = Self::new_zero(self.width, b.width);
+        for y in 0..b.width {
+            for (row, value) in lu.solve_vec(&b.column(y)).into_iter().enumerate() {
+                x[(row, y)] = value;
+            }
+        }
+        Ok(x)
+    }
+
+    pub fn correct(&mut self) {
+        //snaps parts that are within rounding of an integer
+        for elem in self.data.iter_mut() {
+            *elem = elem.map_parts(|part| {
+                let x = part.to_f64();
+                if x - x.floor() > 0.999999 || (x > -0.00001 && x < 0.00001) {
+                    T::Real::from_f64(x.round())
+                } else {
+                    part
+                }
+            });
+        }
+    }
+}
+
+//gap between 1 and the next value in the real type behind T, so that
+//tolerances follow the precision the entries are stored in
+fn epsilon<T: DivisionRing>() -> f64 {
+    T::Real::epsilon().to_f64()
+}
+
+//T from a real number, for the factorizations below
+fn scalar<T: ComplexField>(x: f64) -> T {
+    T::from_real(T::Real::from_f64(x))
+}
+
+//sum of conj(a_i) b_i
+fn dot<T: DivisionRing>(a: &[T], b: &[T]) -> T {
+    a.iter().zip(b).fold(T::zero(), |acc, (&x, &y)| acc + x.conj() * y)
+}
+
+//orthogonal and triangular factorizations, over the reals and the complex numbers alike
+//for complex entries transposes become conjugate transposes
+impl<T: ComplexField> Matrice<T> {
+    pub fn qr(&self) -> Qr<T> {
+        //Householder reflections, A = QR
+        let (m, n) = (self.height, self.width);
+        let two: T = scalar(2.0);
+        let mut a = self.clone();
+        let mut householders: Vec<Vec<T>> = Vec::new();
+        for k in 0..m.min(n) {
+            let norm = (k..m).map(|i| a[(i, k)].norm_sqr().to_f64()).sum::<f64>().sqrt();
+            let mut v: Vec<T> = (k..m).map(|i| a[(i, k)]).collect();
+            if norm != 0.0 {
+                //reflect onto -phase(x0) |x| e1 so v[0] does not cancel
+                let phase = if v[0].is_zero() {T::one()} else {v[0] * scalar(1.0 / v[0].abs().to_f64())};
+                v[0] = v[0] + phase * scalar(norm);
+                let len = v.iter().map(|x| x.norm_sqr().to_f64()).sum::<f64>().sqrt();
+                v.iter_mut().for_each(|x| *x = *x * scalar(1.0 / len));
+                let s: Vec<T> = (k..n).map(|j| (k..m).fold(T::zero(), |acc, i| acc + v[i - k].conj() * a[(i, j)])).collect();
+                for (&vi, row) in v.iter().zip(a.rows_mut().skip(k)) {
+                    for (x, &sj) in row[k..].iter_mut().zip(&s) {
+                        *x = *x - two * vi * sj;
+                    }
+                }
+            } else {
+                v.iter_mut().for_each(|x| *x = T::zero());
+            }
+            for row in a.rows_mut().skip(k + 1) {
+                row[k] = T::zero();
+            }
+            householders.push(v);
+        }
+        let scale = self.data.iter().fold(0.0, |m: f64, x| m.max(x.abs().to_f64()));
+        Qr {
+            householders,
+            r: a,
+            tolerance: m.max(n) as f64 * epsilon::<T>() * scale
+        }
+    }
+
+    pub fn lstsq(&self, b: &[T]) -> std::result::Result<Vec<T>, MatrixError> {
+        //x minimizing |self * x - b|, for tall self of full column rank
+        if self.height < self.width {
+            return Err(MatrixError::Underdetermined { shape: (self.height, self.width) });
+        }
+        if b.len() != self.height {
+            return Err(MatrixError::DimensionMismatch { left: (self.height, self.width), right: (b.len(), 1) });
+        }
+        self.qr().solve_lstsq(b)
+    }
+
+    pub fn cholesky(&self) -> std::result::Result<Matrice<T>, MatrixError> {
+        //lower triangular L with A = L L^H, only the lower triangle of A is read
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        let n = self.height;
+        let mut l = Self::new_zero(n, n);
+        for j in 0..n {
+            //the diagonal of a hermitian matrix is real
+            let d = self[(j, j)].re().to_f64() - (0..j).map(|k| l[(j, k)].norm_sqr().to_f64()).sum::<f64>();
+            if d <= 0.0 || d.is_nan() {
+                return Err(MatrixError::NotPositiveDefinite);
+            }
+            l[(j, j)] = scalar(d.sqrt());
+            for i in j+1..n {
+                let s = (0..j).fold(self[(i, j)], |acc, k| acc - l[(i, k)] * l[(j, k)].conj());
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+        Ok(l)
+    }
+
+    pub fn svd(&self) -> Svd<T> {
+        //one-sided Jacobi, orthogonalizes the columns of A by plane rotations
+        //accumulated in V, the column norms are then the singular values
+        if self.height < self.width {
+            //A^H = U S V^H gives A = V S U^H
+            let t = self.adjoint().svd();
+            return Svd {
+                u: t.v,
+                s: t.s,
+                v: t.u
+            };
+        }
+        let (m, n) = (self.height, self.width);
+        let mut u = self.clone();
+        let mut v = Self::identitymat(n);
+        for _sweep in 0..60 {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p+1..n {
+                    let alpha: f64 = (0..m).map(|i| u[(i, p)].norm_sqr().to_f64()).sum();
+                    let beta: f64 = (0..m).map(|i| u[(i, q)].norm_sqr().to_f64()).sum();
+                    let gamma = (0..m).fold(T::zero(), |acc, i| acc + u[(i, p)].conj() * u[(i, q)]);
+                    let g = gamma.abs().to_f64();
+                    if g == 0.0 || g <= epsilon::<T>() * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+                    //turning column q by the phase of gamma leaves a real rotation
+                    let phase = gamma.conj() * scalar(1.0 / g);
+                    let zeta = (beta - alpha) / (2.0 * g);
+                    let t = 1.0f64.copysign(zeta) / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let c = 1.0 / (1.0 + t * t).sqrt();
+                    let (c, s): (T, T) = (scalar(c), scalar(c * t));
+                    for row in u.rows_mut().chain(v.rows_mut()) {
+                        let (xp, xq) = (row[p], row[q] * phase);
+                        row[p] = c * xp - s * xq;
+                        row[q] = s * xp + c * xq;
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+        let norms: Vec<f64> = (0..n).map(|j| (0..m).map(|i| u[(i, j)].norm_sqr().to_f64()).sum::<f64>().sqrt()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&a, &b| norms[b].total_cmp(&norms[a]));
+        let mut svd = Svd {
+            u: Self::new_zero(m, n),
+            s: order.iter().map(|&j| norms[j]).collect(),
+            v: Self::new_zero(n, n)
+        };
+        for (col, &j) in order.iter().enumerate() {
+            for (target, row) in svd.u.rows_mut().zip(u.rows()) {
+                target[col] = if norms[j] != 0.0 {row[j] * scalar(1.0 / norms[j])} else {T::zero()};
+            }
+            for (target, row) in svd.v.rows_mut().zip(v.rows()) {
+                target[col] = row[j];
+            }
+        }
+        svd
+    }
+
+    pub fn singular_values(&self) -> Vec<f64> {
+        self.svd().s
+    }
+
+    pub fn rank(&self) -> usize {
+        self.svd().rank()
+    }
+
+    pub fn cond(&self) -> f64 {
+        self.svd().condition_number()
+    }
+
+    pub fn pinv(&self) -> Matrice<T> {
+        self.svd().pseudo_inverse()
+    }
+}
+
+//the eigensolvers work on real matrices only, the Hessenberg QR iteration behind
+//eigen relies on real Francis double shifts
+//they are also f64 only: the Jacobi and hqr2 kernels below run in f64 with f64::EPSILON
+//as their convergence threshold, and the results are ComplexNumber and f64,
+//so a Matrice<f32> has to be widened entry by entry (as_slice and from_vec) first
+impl Matrix {
+    pub fn eigen(&self) -> std::result::Result<Eigen, MatrixError> {
+        //Hessenberg reduction and shifted QR iteration, symmetric input goes through Jacobi
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        if self.is_symmetric() {
+            let sym = jacobi_eigen(self);
+            let n = self.height;
+            let mut vectors = Matrice::<ComplexNumber>::new_zero(n, n);
+            for x in 0..n {
+                for y in 0..n {
+                    vectors[(x, y)] = complex!(sym.vectors[(x, y)], 0);
+                }
+            }
+            return Ok(Eigen {
+                values: sym.values.iter().map(|&x| complex!(x, 0)).collect(),
+                vectors
+            });
+        }
+        Ok(general_eigen(self))
+    }
+
+    pub fn symmetric_eigen(&self) -> std::result::Result<SymmetricEigen, MatrixError> {
+        //real eigenvalues and an orthonormal eigenbasis, only for symmetric input
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare { shape: (self.height, self.width) });
+        }
+        if !self.is_symmetric() {
+            return Err(MatrixError::NotSymmetric);
+        }
+        Ok(jacobi_eigen(self))
+    }
+}
+//LU START
+pub struct Lu<T> {
+    //L (unit diagonal, stored below it) and U packed in one matrix
+    pub lu: Matrice<T>,
+    //row i of PA is row perm[i] of A
+    pub perm: Vec<usize>,
+    //+1 or -1, the parity of perm
+    pub sign: f64,
+    //pivots this small count as zero
+    pub tolerance: f64
+}
+
+impl<T: DivisionRing> Lu<T> {
+    pub fn l(&self) -> Matrice<T> {
+        let n = self.lu.height;
+        let mut l = Matrice::identitymat(n);
+        for x in 0..n {
+            for y in 0..x {
+                l[(x, y)] = self.lu[(x, y)];
+            }
+        }
+        l
+    }
+
+    pub fn u(&self) -> Matrice<T> {
+        let n = self.lu.height;
+        let mut u = Matrice::new_zero(n, n);
+        for x in 0..n {
+            for y in x..n {
+                u[(x, y)] = self.lu[(x, y)];
+            }
+        }
+        u
+    }
+
+    pub fn det(&self) -> T {
+        //product of the pivots in order, for quaternions this is not multiplicative
+        let d = (0..self.lu.height).fold(T::one(), |d, k| d * self.lu[(k, k)]);
+        if self.sign < 0.0 {T::zero() - d} else {d}
+    }
+
+    pub fn is_singular(&self) -> bool {
+        (0..self.lu.height).any(|k| self.lu[(k, k)].abs().to_f64() <= self.tolerance)
+    }
+
+    pub fn solve_vec(&self, b: &[T]) -> Vec<T> {
+        //forward substitution with L, then back substitution with U
+        let n = self.lu.height;
+        let a = &self.lu;
+        let mut x: Vec<T> = self.perm.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] = x[i] - a[(i, j)] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i+1..n {
+                x[i] = x[i] - a[(i, j)] * x[j];
+            }
+            x[i] = x[i].div_left(a[(i, i)]);
+        }
+        x
+    }
+
+    pub fn inverse(&self) -> Option<Matrice<T>> {
+        if self.is_singular() {
+            return None;
+        }
+        let n = self.lu.height;
+        let mut inv = Matrice::new_zero(n, n);
+        for y in 0..n {
+            let mut e = vec![T::zero(); n];
+            e[y] = T::one();
+            for (x, value) in self.solve_vec(&e).into_iter().enumerate() {
+                inv[(x, y)] = value;
+            }
+        }
+        Some(inv)
+    }
+}
+//LU END
+
+//QR START
+pub struct Qr<T = f64> {
+    //unit Householder vectors, the k-th one acts on rows k.. and Q = H_0 H_1 ...
+    pub householders: Vec<Vec<T>>,
+    //upper trapezoidal, same shape as the factored matrix
+    pub r: Matrice<T>,
+    //diagonal entries of R this small count as zero
+    pub tolerance: f64
+}
+
+impl<T: ComplexField> Qr<T> {
+    pub fn apply_qt(&self, b: &[T]) -> Vec<T> {
+        //Q^H b (Q^T b for real matrices), applying the reflections in order
+        let two: T = scalar(2.0);
+        let mut y = b.to_vec();
+        for (k, v) in self.householders.iter().enumerate() {
+            let s = dot(v, &y[k..]);
+            for (&vi, yi) in v.iter().zip(y[k..].iter_mut()) {
+                *yi = *yi - two * vi * s;
+            }
+        }
+        y
+    }
+
+    pub fn q(&self) -> Matrice<T> {
+        //the full m x m unitary factor, the reflections applied to I last one first
+        let m = self.r.height;
+        let two: T = scalar(2.0);
+        let mut q = Matrice::identitymat(m);
+        for (k, v) in self.householders.iter().enumerate().rev() {
+            for j in 0..m {
+                let s = (k..m).fold(T::zero(), |acc, i| acc + v[i - k].conj() * q[(i, j)]);
+                for i in k..m {
+                    q[(i, j)] = q[(i, j)] - two * v[i - k] * s;
+                }
+            }
+        }
+        q
+    }
+
+    pub fn is_full_rank(&self) -> bool {
+        (0..self.r.height.min(self.r.width)).all(|k| self.r[(k, k)].abs().to_f64() > self.tolerance)
+    }
+
+    pub fn solve_lstsq(&self, b: &[T]) -> std::result::Result<Vec<T>, MatrixError> {
+        //R x = (Q^H b) restricted to the first n rows
+        if !self.is_full_rank() || self.r.height < self.r.width {
+            return Err(MatrixError::RankDeficient);
+        }
+        let n = self.r.width;
+        let r = &self.r;
+        let mut x = self.apply_qt(b);
+        x.truncate(n);
+        for i in (0..n).rev() {
+            for j in i+1..n {
+                x[i] = x[i] - r[(i, j)] * x[j];
+            }
+            x[i] = x[i] / r[(i, i)];
+        }
+        Ok(x)
+    }
+}
+//QR END
+
+//SVD START
+pub struct Svd<T = f64> {
+    //m x k with orthonormal columns (a column is zero when its singular value is)
+    pub u: Matrice<T>,
+    //the k = min(m, n) singular values, largest first
+    pub s: Vec<f64>,
+    //n x k with orthonormal columns
+    pub v: Matrice<T>
+}
+
+impl<T: ComplexField> Svd<T> {
+    pub fn tolerance(&self) -> f64 {
+        let largest = if self.s.is_empty() {0.0} else {self.s[0]};
+        self.u.height.max(self.v.height) as f64 * epsilon::<T>() * largest
+    }
+
+    pub fn rank(&self) -> usize {
+        let tolerance = self.tolerance();
+        self.s.iter().filter(|&&x| x > tolerance).count()
+    }
+
+    pub fn condition_number(&self) -> f64 {
+        //ratio of the largest to the smallest singular value, infinite when rank deficient
+        match (self.s.first(), self.s.last()) {
+            (Some(&largest), Some(&smallest)) if smallest > self.tolerance() => largest / smallest,
+            _ => f64::INFINITY
+        }
+    }
+
+    pub fn pseudo_inverse(&self) -> Matrice<T> {
+        //V S^+ U^H, singular values under the rank tolerance are treated as zero
+        let (m, n) = (self.u.height, self.v.height);
+        let tolerance = self.tolerance();
+        let mut pinv = Matrice::new_zero(n, m);
+        for (k, &sk) in self.s.iter().enumerate() {
+            if sk <= tolerance {
+                continue;
+            }
+            let inv: T = scalar(1.0 / sk);
+            for i in 0..n {
+                for j in 0..m {
+                    pinv[(i, j)] = pinv[(i, j)] + self.v[(i, k)] * inv * self.u[(j, k)].conj();
+                }
+            }
+        }
+        pinv
+    }
+}
+//SVD END
+
+//EIGEN START
+pub struct Eigen {
+    //eigenvalues in no particular order, complex ones come in conjugate pairs
+    pub values: Vec<ComplexNumber>,
+    //column k is a unit eigenvector for values[k]
+    pub vectors: Matrice<ComplexNumber>
+}
+
+pub struct SymmetricEigen {
+    //real eigenvalues in ascending order
+    pub values: Vec<f64>,
+    //orthonormal columns, column k belongs to values[k]
+    pub vectors: Matrix
+}
+
+fn jacobi_eigen(m: &Matrix) -> SymmetricEigen {
+    //cyclic Jacobi, rotates away one off-diagonal pair at a time
+    let n = m.height;
+    let mut a = m.clone();
+    let mut v = Matrix::identitymat(n);
+    let scale = a.data.iter().fold(0.0, |s: f64, x| s.max(x.abs()));
+    for _sweep in 0..100 {
+        let off: f64 = (0..n).map(|i| (0..n).filter(|&j| j != i).map(|j| a[(i, j)] * a[(i, j)]).sum::<f64>()).sum();
+        if off.sqrt() <= f64::EPSILON * scale {
+            break;
+        }
+        for p in 0..n {
+            for q in p+1..n {
+                if a[(p, q)] == 0.0 {
+                    continue;
+                }
+                let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                let t = 1.0f64.copysign(theta) / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.rows_mut().chain(v.rows_mut()) {
+                    let (xp, xq) = (row[p], row[q]);
+                    row[p] = c * xp - s * xq;
+                    row[q] = s * xp + c * xq;
+                }
+                for j in 0..n {
+                    let (yp, yq) = (a[(p, j)], a[(q, j)]);
+                    a[(p, j)] = c * yp - s * yq;
+                    a[(q, j)] = s * yp + c * yq;
+                }
+            }
+        }
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&x, &y| a[(x, x)].total_cmp(&a[(y, y)]));
+    let mut vectors = matrix!(n, n);
+    for (col, &k) in order.iter().enumerate() {
+        for (target, row) in vectors.rows_mut().zip(v.rows()) {
+            target[col] = row[k];
+        }
+    }
+    SymmetricEigen {
+        values: order.iter().map(|&k| a[(k, k)]).collect(),
+        vectors
+    }
+}
+
+fn cdiv(xr: f64, xi: f64, yr: f64, yi: f64) -> (f64, f64) {
+    //(xr + xi i) / (yr + yi i) without squaring the denominator
+    if yr.abs() > yi.abs() {
+        let r = yi / yr;
+        let d = yr + r * yi;
+        ((xr + r * xi) / d, (xi - r * xr) / d)
+    } else {
+        let r = yr / yi;
+        let d = yi + r * yr;
+        ((r * xr + xi) / d, (r * xi - xr) / d)
+    }
+}
+
+fn hessenberg(h: &mut Matrix) -> Matrix {
+    //orthogonal reduction to upper Hessenberg form by Householder reflections,
+    //returns the accumulated transformation
+    let n = h.height;
+    let mut ort = vec![0.0; n];
+    let high = n - 1;
+    for m in 1..high {
+        let scale: f64 = (m..=high).map(|i| h[(i, m-1)].abs()).sum();
+        if scale == 0.0 {
+            continue;
+        }
+        let mut hh = 0.0;
+        for i in (m..=high).rev() {
+            ort[i] = h[(i, m-1)] / scale;
+            hh += ort[i] * ort[i];
+        }
+        let g = if ort[m] > 0.0 {-hh.sqrt()} else {hh.sqrt()};
+        hh -= ort[m] * g;
+        ort[m] -= g;
+        for j in m..n {
+            let f = (m..=high).rev().map(|i| ort[i] * h[(i, j)]).sum::<f64>() / hh;
+            for i in m..=high {
+                h[(i, j)] -= f * ort[i];
+            }
+        }
+        for i in 0..=high {
+            let f = (m..=high).rev().map(|j| ort[j] * h[(i, j)]).sum::<f64>() / hh;
+            for j in m..=high {
+                h[(i, j)] -= f * ort[j];
+            }
+        }
+        ort[m] *= scale;
+        h[(m, m-1)] = scale * g;
+    }
+    let mut v = Matrix::identitymat(n);
+    for m in (1..high).rev() {
+        if h[(m, m-1)] == 0.0 {
+            continue;
+        }
+        for i in m+1..=high {
+            ort[i] = h[(i, m-1)];
+        }
+        for j in m..=high {
+            let g = (m..=high).map(|i| ort[i] * v[(i, j)]).sum::<f64>();
+            //double division avoids possible underflow
+            let g = (g / ort[m]) / h[(m, m-1)];
+            for i in m..=high {
+                v[(i, j)] += g * ort[i];
+            }
+        }
+    }
+    v
+}
+
+fn hqr2(h: &mut Matrix, v: &mut Matrix) -> (Vec<f64>, Vec<f64>) {
+    //shifted double QR iteration on the Hessenberg matrix h, followed by back
+    //substitution for the eigenvectors (the EISPACK hqr2 scheme, as in JAMA)
+    //returns real and imaginary parts of the eigenvalues, v ends up holding
+    //the eigenvectors with complex pairs split over two columns (re, im)
+    let nn = h.height;
+    let mut d = vec![0.0; nn];
+    let mut e = vec![0.0; nn];
+    let eps = f64::EPSILON;
+    let mut exshift = 0.0;
+    let (mut p, mut q, mut r, mut s, mut z) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    let (mut w, mut x, mut y);
+
+    let mut norm = 0.0;
+    for i in 0..nn {
+        for j in i.saturating_sub(1)..nn {
+            norm += h[(i, j)].abs();
+        }
+    }
+
+    let mut n = nn as isize - 1;
+    let mut iter = 0;
+    while n >= 0 {
+        let nu = n as usize;
+        //look for a single small sub-diagonal element
+        let mut l = n;
+        while l > 0 {
+            let lu = l as usize;
+            s = h[(lu-1, lu-1)].abs() + h[(lu, lu)].abs();
+            if s == 0.0 {
+                s = norm;
+            }
+            if h[(lu, lu-1)].abs() < eps * s {
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == n {
+            //one root found
+            h[(nu, nu)] += exshift;
+            d[nu] = h[(nu, nu)];
+            e[nu] = 0.0;
+            n -= 1;
+            iter = 0;
+        } else if l == n - 1 {
+            //two roots found
+            w = h[(nu, nu-1)] * h[(nu-1, nu)];
+            p = (h[(nu-1, nu-1)] - h[(nu, nu)]) / 2.0;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            h[(nu, nu)] += exshift;
+            h[(nu-1, nu-1)] += exshift;
+            x = h[(nu, nu)];
+            if q >= 0.0 {
+                //real pair
+                z = if p >= 0.0 {p + z} else {p - z};
+                d[nu-1] = x + z;
+                d[nu] = d[nu-1];
+                if z != 0.0 {
+                    d[nu] = x - w / z;
+                }
+                e[nu-1] = 0.0;
+                e[nu] = 0.0;
+                x = h[(nu, nu-1)];
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = (p * p + q * q).sqrt();
+                p /= r;
+                q /= r;
+                for j in nu-1..nn {
+                    z = h[(nu-1, j)];
+                    h[(nu-1, j)] = q * z + p * h[(nu, j)];
+                    h[(nu, j)] = q * h[(nu, j)] - p * z;
+                }
+                for i in 0..=nu {
+                    z = h[(i, nu-1)];
+                    h[(i, nu-1)] = q * z + p * h[(i, nu)];
+                    h[(i, nu)] = q * h[(i, nu)] - p * z;
+                }
+                for row in v.rows_mut() {
+                    z = row[nu-1];
+                    row[nu-1] = q * z + p * row[nu];
+                    row[nu] = q * row[nu] - p * z;
+                }
+            } else {
+                //complex pair
+                d[nu-1] = x + p;
+                d[nu] = x + p;
+                e[nu-1] = z;
+                e[nu] = -z;
+            }
+            n -= 2;
+            iter = 0;
+        } else {
+            //no convergence yet, form the shift
+            x = h[(nu, nu)];
+            y = 0.0;
+            w = 0.0;
+            if l < n {
+                y = h[(nu-1, nu-1)];
+                w = h[(nu, nu-1)] * h[(nu-1, nu)];
+            }
+            if iter == 10 {
+                //Wilkinson's original ad hoc shift
+                exshift += x;
+                for i in 0..=nu {
+                    h[(i, i)] -= x;
+                }
+                s = h[(nu, nu-1)].abs() + h[(nu-1, nu-2)].abs();
+                x = 0.75 * s;
+                y = x;
+                w = -0.4375 * s * s;
+            }
+            if iter == 30 {
+                //MATLAB's ad hoc shift
+                s = (y - x) / 2.0;
+                s = s * s + w;
+                if s > 0.0 {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / 2.0 + s);
+                    for i in 0..=nu {
+                        h[(i, i)] -= s;
+                    }
+                    exshift += s;
+                    x = 0.964;
+                    y = x;
+                    w = x;
+                }
+            }
+            iter += 1;
+            if iter > 30 * nn + 30 {
+                //no convergence, leave the remaining eigenvalues as NaN
+                for k in 0..=nu {
+                    d[k] = f64::NAN;
+                    e[k] = f64::NAN;
+                }
+                break;
+            }
+
+            //look for two consecutive small sub-diagonal elements
+            let mut m = n - 2;
+            while m >= l {
+                let mu = m as usize;
+                z = h[(mu, mu)];
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / h[(mu+1, mu)] + h[(mu, mu+1)];
+                q = h[(mu+1, mu+1)] - z - r - s;
+                r = h[(mu+2, mu+1)];
+                s = p.abs() + q.abs() + r.abs();
+                p /= s;
+                q /= s;
+                r /= s;
+                if m == l {
+                    break;
+                }
+                if h[(mu, mu-1)].abs() * (q.abs() + r.abs()) <
+                    eps * (p.abs() * (h[(mu-1, mu-1)].abs() + z.abs() + h[(mu+1, mu+1)].abs())) {
+                    break;
+                }
+                m -= 1;
+            }
+            let mu = m as usize;
+            for i in mu+2..=nu {
+                h[(i, i-2)] = 0.0;
+                if i > mu + 2 {
+                    h[(i, i-3)] = 0.0;
+                }
+            }
+
+            //double QR step involving rows l..n and columns m..n
+            for k in mu..nu {
+                let notlast = k != nu - 1;
+                if k != mu {
+                    p = h[(k, k-1)];
+                    q = h[(k+1, k-1)];
+                    r = if notlast {h[(k+2, k-1)]} else {0.0};
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == 0.0 {
+                        continue;
+                    }
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+                s = (p * p + q * q + r * r).sqrt();
+                if p < 0.0 {
+                    s = -s;
+                }
+                if s == 0.0 {
+                    continue;
+                }
+                if k != mu {
+                    h[(k, k-1)] = -s * x;
+                } else if l != m {
+                    h[(k, k-1)] = -h[(k, k-1)];
+                }
+                p += s;
+                x = p / s;
+                y = q / s;
+                z = r / s;
+                q /= p;
+                r /= p;
+                for j in k..nn {
+                    p = h[(k, j)] + q * h[(k+1, j)];
+                    if notlast {
+                        p += r * h[(k+2, j)];
+                        h[(k+2, j)] -= p * z;
+                    }
+                    h[(k, j)] -= p * x;
+                    h[(k+1, j)] -= p * y;
+                }
+                for i in 0..=nu.min(k + 3) {
+                    p = x * h[(i, k)] + y * h[(i, k+1)];
+                    if notlast {
+                        p += z * h[(i, k+2)];
+                        h[(i, k+2)] -= p * r;
+                    }
+                    h[(i, k)] -= p;
+                    h[(i, k+1)] -= p * q;
+                }
+                for row in v.rows_mut() {
+                    p = x * row[k] + y * row[k+1];
+                    if notlast {
+                        p += z * row[k+2];
+                        row[k+2] -= p * r;
+                    }
+                    row[k] -= p;
+                    row[k+1] -= p * q;
+                }
+            }
+        }
+    }
+
+    //back substitute to find the vectors of the upper triangular form
+    if norm == 0.0 || d.iter().any(|x| x.is_nan()) {
+        return (d, e);
+    }
+    for n in (0..nn).rev() {
+        p = d[n];
+        q = e[n];
+        if q == 0.0 {
+            //real vector
+            let mut l = n;
+            h[(n, n)] = 1.0;
+            for i in (0..n).rev() {
+                w = h[(i, i)] - p;
+                r = (l..=n).map(|j| h[(i, j)] * h[(j, n)]).sum();
+                if e[i] < 0.0 {
+                    z = w;
+                    s = r;
+                } else {
+                    l = i;
+                    if e[i] == 0.0 {
+                        h[(i, n)] = if w != 0.0 {-r / w} else {-r / (eps * norm)};
+                    } else {
+                        //solve real equations
+                        x = h[(i, i+1)];
+                        y = h[(i+1, i)];
+                        q = (d[i] - p) * (d[i] - p) + e[i] * e[i];
+                        let t = (x * s - z * r) / q;
+                        h[(i, n)] = t;
+                        h[(i+1, n)] = if x.abs() > z.abs() {(-r - w * t) / x} else {(-s - y * t) / z};
+                    }
+                    //overflow control
+                    let t = h[(i, n)].abs();
+                    if (eps * t) * t > 1.0 {
+                        for j in i..=n {
+                            h[(j, n)] /= t;
+                        }
+                    }
+                }
+            }
+        } else if q < 0.0 {
+            //complex vector, the last component is imaginary so the matrix is triangular
+            let mut l = n - 1;
+            if h[(n, n-1)].abs() > h[(n-1, n)].abs() {
+                h[(n-1, n-1)] = q / h[(n, n-1)];
+                h[(n-1, n)] = -(h[(n, n)] - p) / h[(n, n-1)];
+            } else {
+                let (cr, ci) = cdiv(0.0, -h[(n-1, n)], h[(n-1, n-1)] - p, q);
+                h[(n-1, n-1)] = cr;
+                h[(n-1, n)] = ci;
+            }
+            h[(n, n-1)] = 0.0;
+            h[(n, n)] = 1.0;
+            for i in (0..n-1).rev() {
+                let ra: f64 = (l..=n).map(|j| h[(i, j)] * h[(j, n-1)]).sum();
+                let sa: f64 = (l..=n).map(|j| h[(i, j)] * h[(j, n)]).sum();
+                w = h[(i, i)] - p;
+                if e[i] < 0.0 {
+                    z = w;
+                    r = ra;
+                    s = sa;
+                } else {
+                    l = i;
+                    if e[i] == 0.0 {
+                        let (cr, ci) = cdiv(-ra, -sa, w, q);
+                        h[(i, n-1)] = cr;
+                        h[(i, n)] = ci;
+                    } else {
+                        //solve complex equations
+                        x = h[(i, i+1)];
+                        y = h[(i+1, i)];
+                        let mut vr = (d[i] - p) * (d[i] - p) + e[i] * e[i] - q * q;
+                        let vi = (d[i] - p) * 2.0 * q;
+                        if vr == 0.0 && vi == 0.0 {
+                            vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                        }
+                        let (cr, ci) = cdiv(x * r - z * ra + q * sa, x * s - z * sa - q * ra, vr, vi);
+                        h[(i, n-1)] = cr;
+                        h[(i, n)] = ci;
+                        if x.abs() > z.abs() + q.abs() {
+                            h[(i+1, n-1)] = (-ra - w * h[(i, n-1)] + q * h[(i, n)]) / x;
+                            h[(i+1, n)] = (-sa - w * h[(i, n)] - q * h[(i, n-1)]) / x;
+                        } else {
+                            let (cr, ci) = cdiv(-r - y * h[(i, n-1)], -s - y * h[(i, n)], z, q);
+                            h[(i+1, n-1)] = cr;
+                            h[(i+1, n)] = ci;
+                        }
+                    }
+                    //overflow control
+                    let t = h[(i, n-1)].abs().max(h[(i, n)].abs());
+                    if (eps * t) * t > 1.0 {
+                        for j in i..=n {
+                            h[(j, n-1)] /= t;
+                            h[(j, n)] /= t;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    //back transformation to the eigenvectors of the original matrix
+    for j in (0..nn).rev() {
+        for row in v.rows_mut() {
+            let z: f64 = (0..=j).map(|k| row[k] * h[(k, j)]).sum();
+            row[j] = z;
+        }
+    }
+    (d, e)
+}
+
+fn general_eigen(m: &Matrix) -> Eigen {
+    let n = m.height;
+    let mut h = m.clone();
+    let mut v = hessenberg(&mut h);
+    let (d, e) = hqr2(&mut h, &mut v);
+    let mut vectors = Matrice::<ComplexNumber>::new_zero(n, n);
+    let mut k = 0;
+    while k < n {
+        if e[k] == 0.0 || e[k].is_nan() {
+            for (target, row) in vectors.rows_mut().zip(v.rows()) {
+                target[k] = complex!(row[k], 0);
+            }
+            k += 1;
+        } else {
+            //columns k and k+1 hold the real and imaginary parts for d[k] + e[k] i
+            for (target, row) in vectors.rows_mut().zip(v.rows()) {
+                target[k] = complex!(row[k], row[k+1]);
+                target[k+1] = complex!(row[k], -row[k+1]);
+            }
+            k += 2;
+        }
+    }
+    for k in 0..n {
+        let len = (0..n).map(|i| vectors[(i, k)].range().powi(2)).sum::<f64>().sqrt();
+        if len != 0.0 {
+            for row in vectors.rows_mut() {
+                row[k] = row[k].apply(|x| {x / len});
+            }
+        }
+    }
+    Eigen {
+        values: d.iter().zip(&e).map(|(&re, &im)| complex!(re, im)).collect(),
+        vectors
+    }
+}
+//EIGEN END
+//MATRIX END
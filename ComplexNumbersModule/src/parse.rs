@@ -0,0 +1,125 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::fmt::Debug;
+use std::str::FromStr;
+use crate::traits::Real;
+
+//PARSING START
+//positions are 0-based character offsets into the parsed string
+pub enum ParseNumberError {
+    Empty,
+    UnexpectedEnd { position: usize },
+    UnexpectedChar { position: usize, found: char },
+    InvalidNumber { position: usize, text: String },
+    RepeatedPart { position: usize }
+}
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ParseNumberError::Empty => write!(f, "cannot parse a number from an empty string"),
+            ParseNumberError::UnexpectedEnd { position } =>
+                write!(f, "unexpected end of input at {}", position),
+            ParseNumberError::UnexpectedChar { position, found } =>
+                write!(f, "unexpected '{}' at {}", found, position),
+            ParseNumberError::InvalidNumber { position, text } =>
+                write!(f, "invalid number \"{}\" at {}", text, position),
+            ParseNumberError::RepeatedPart { position } =>
+                write!(f, "part at {} was already given", position)
+        }
+    }
+}
+
+impl Debug for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for ParseNumberError {}
+
+//reads a sum of signed terms like "1.5e3-2i+j", units maps each unit letter to
+//the index of its part, index 0 being the real part
+pub fn parse_parts<T: Real + FromStr>(s: &str, units: &[(char, usize)]) -> std::result::Result<[T; 4], ParseNumberError> {
+    let chars: Vec<char> = s.chars().collect();
+    let skip_whitespace = |mut pos: usize| {
+        while pos < chars.len() && chars[pos].is_whitespace() {
+            pos += 1;
+        }
+        pos
+    };
+    let digits_from = |mut pos: usize| {
+        while pos < chars.len() && chars[pos].is_ascii_digit() {
+            pos += 1;
+        }
+        pos
+    };
+    let keyword_at = |pos: usize, word: &str| {
+        word.chars().enumerate().all(|(n, c)| {chars.get(pos + n).is_some_and(|x| x.to_ascii_lowercase() == c)})
+    };
+
+    let mut parts = [T::zero(); 4];
+    let mut seen = [false; 4];
+    let mut pos = skip_whitespace(0);
+    if pos == chars.len() {
+        return Err(ParseNumberError::Empty);
+    }
+    let mut first = true;
+    while pos < chars.len() {
+        let term_start = pos;
+        let negative = chars[pos] == '-';
+        if chars[pos] == '+' || chars[pos] == '-' {
+            pos = skip_whitespace(pos + 1);
+        } else if !first {
+            return Err(ParseNumberError::UnexpectedChar { position: pos, found: chars[pos] });
+        }
+        first = false;
+
+        let number_start = pos;
+        if pos < chars.len() && (chars[pos].is_ascii_digit() || chars[pos] == '.') {
+            pos = digits_from(pos);
+            if pos < chars.len() && chars[pos] == '.' {
+                pos = digits_from(pos + 1);
+            }
+            if pos < chars.len() && (chars[pos] == 'e' || chars[pos] == 'E') {
+                let exponent = if pos + 1 < chars.len() && (chars[pos + 1] == '+' || chars[pos + 1] == '-') {pos + 2} else {pos + 1};
+                if exponent < chars.len() && chars[exponent].is_ascii_digit() {
+                    pos = digits_from(exponent);
+                }
+            }
+        } else if let Some(word) = ["infinity", "inf", "nan"].iter().find(|w| keyword_at(pos, w)) {
+            pos += word.len();
+        }
+        let value = if pos > number_start {
+            let text: String = chars[number_start..pos].iter().collect();
+            match text.parse::<T>() {
+                Ok(x) => Some(x),
+                Err(_) => return Err(ParseNumberError::InvalidNumber { position: number_start, text })
+            }
+        } else {
+            None
+        };
+
+        pos = skip_whitespace(pos);
+        let unit = if pos < chars.len() {units.iter().find(|(c, _)| *c == chars[pos])} else {None};
+        let index = match (unit, value) {
+            (Some(&(_, index)), _) => {
+                pos += 1;
+                index
+            }
+            (None, Some(_)) => 0,
+            (None, None) if pos == chars.len() => return Err(ParseNumberError::UnexpectedEnd { position: pos }),
+            (None, None) => return Err(ParseNumberError::UnexpectedChar { position: pos, found: chars[pos] })
+        };
+        if seen[index] {
+            return Err(ParseNumberError::RepeatedPart { position: term_start });
+        }
+        let value = value.unwrap_or(T::one());
+        parts[index] = if negative {-value} else {value};
+        seen[index] = true;
+        pos = skip_whitespace(pos);
+    }
+    Ok(parts)
+}
+//PARSING END
@@ -0,0 +1,348 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::fmt::Debug;
+use core::ops::Add;
+use core::ops::Sub;
+use std::ops::Mul;
+use std::ops::Div;
+use std::ops::Rem;
+use std::ops::Neg;
+use crate::traits::*;
+use crate::complex::*;
+use crate::matrix::Matrice;
+
+//POLYNOMIAL START
+//coefficients in ascending order, coeffs[k] multiplies x^k, with no trailing zeros
+//so the zero polynomial has no coefficients at all
+pub struct Polynomial<T = f64> {
+    coeffs: Vec<T>
+}
+
+impl<T: Ring> Polynomial<T> {
+    pub fn new(coeffs: Vec<T>) -> Self {
+        let mut polynomial = Polynomial { coeffs };
+        polynomial.trim();
+        polynomial
+    }
+
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    pub fn constant(c: T) -> Self {
+        Polynomial::new(vec![c])
+    }
+
+    //x, the identity polynomial
+    pub fn x() -> Self {
+        Polynomial::new(vec![T::zero(), T::one()])
+    }
+
+    //(x - r0)(x - r1)...
+    pub fn from_roots(roots: &[T]) -> Self {
+        roots.iter().fold(Polynomial::constant(T::one()), |p, &r| {
+            p * Polynomial::new(vec![T::zero() - r, T::one()])
+        })
+    }
+
+    fn trim(&mut self) {
+        while self.coeffs.last().is_some_and(|c| c.is_zero()) {
+            self.coeffs.pop();
+        }
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    //None for the zero polynomial
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    pub fn leading(&self) -> T {
+        self.coeffs.last().copied().unwrap_or(T::zero())
+    }
+
+    //horner's scheme
+    pub fn eval(&self, x: T) -> T {
+        self.coeffs.iter().rev().fold(T::zero(), |acc, &c| acc * x + c)
+    }
+
+    pub fn derivative(&self) -> Self {
+        //k is built up by adding ones, so this works over any ring
+        let mut k = T::zero();
+        let mut coeffs = Vec::with_capacity(self.coeffs.len().saturating_sub(1));
+        for &c in self.coeffs.iter().skip(1) {
+            k = k + T::one();
+            coeffs.push(k * c);
+        }
+        Polynomial::new(coeffs)
+    }
+
+    pub fn apply(&self, f: impl Fn(T) -> T) -> Self {
+        Polynomial::new(self.coeffs.iter().map(|&c| f(c)).collect())
+    }
+}
+
+impl<T: Field> Polynomial<T> {
+    //quotient and remainder, with the remainder of lower degree than the divisor
+    pub fn div_rem(&self, divisor: &Polynomial<T>) -> (Polynomial<T>, Polynomial<T>) {
+        let d = divisor.degree().expect("polynomial division by zero");
+        let lead = divisor.leading();
+        let mut remainder = self.coeffs.clone();
+        let mut quotient = vec![T::zero(); self.coeffs.len().saturating_sub(d)];
+        for k in (0..quotient.len()).rev() {
+            let q = remainder[k + d] / lead;
+            quotient[k] = q;
+            for (j, &c) in divisor.coeffs.iter().enumerate() {
+                remainder[k + j] = remainder[k + j] - q * c;
+            }
+        }
+        remainder.truncate(d);
+        (Polynomial::new(quotient), Polynomial::new(remainder))
+    }
+
+    //divided by the leading coefficient
+    pub fn monic(&self) -> Self {
+        let lead = self.leading();
+        self.apply(|c| c / lead)
+    }
+
+    //the matrix whose characteristic polynomial is this one made monic, ones below the
+    //diagonal and the negated coefficients in the last column, 0x0 below degree 1
+    pub fn companion(&self) -> Matrice<T> {
+        let n = self.degree().unwrap_or(0);
+        let mut companion = Matrice::new_zero(n, n);
+        if n == 0 {
+            return companion;
+        }
+        let lead = self.leading();
+        for x in 0..n {
+            if x > 0 {
+                companion[(x, x - 1)] = T::one();
+            }
+            companion[(x, n - 1)] = T::zero() - self.coeffs[x] / lead;
+        }
+        companion
+    }
+}
+
+impl<T: ComplexField<Real = f64>> Polynomial<T> {
+    pub fn to_complex(&self) -> Polynomial<ComplexNumber> {
+        Polynomial::new(self.coeffs.iter().map(|&c| complex!(c.re(), c.im())).collect())
+    }
+
+    pub fn eval_complex(&self, z: ComplexNumber) -> ComplexNumber {
+        self.coeffs.iter().rev().fold(ComplexNumber::new(), |acc, &c| acc * z + complex!(c.re(), c.im()))
+    }
+
+    //all roots with multiplicity by the aberth-ehrlich iteration, repeated roots come out
+    //to about the square root of the precision; the zero polynomial gives no roots
+    pub fn roots(&self) -> Vec<ComplexNumber> {
+        let p = self.to_complex();
+        //roots at zero are exact, take out the factor of x^m first
+        let zeros = p.coeffs.iter().take_while(|c| c.is_zero()).count();
+        let mut roots = vec![ComplexNumber::new(); zeros.min(p.coeffs.len().saturating_sub(1))];
+        let p = Polynomial::new(p.coeffs[zeros.min(p.coeffs.len())..].to_vec());
+        let n = match p.degree() {
+            Some(n) if n > 0 => n,
+            _ => return roots
+        };
+        let p = p.monic();
+        if n == 1 {
+            roots.push(-p.coeffs[0]);
+            return roots;
+        }
+        let dp = p.derivative();
+
+        //every root lies within twice the largest |a_k|^(1 / (n - k)), fujiwara's bound
+        let radius = (0..n).map(|k| p.coeffs[k].abs().powf(1.0 / (n - k) as f64)).fold(0.0, f64::max) * 2.0;
+        //start on a circle, turned off the real axis so conjugate pairs can separate
+        let mut z: Vec<ComplexNumber> = (0..n).map(|k| {
+            ComplexNumber::from_polar(radius, 2.0 * std::f64::consts::PI * k as f64 / n as f64 + 0.4)
+        }).collect();
+        for _ in 0..1000 {
+            let mut converged = true;
+            for k in 0..n {
+                let value = p.eval(z[k]);
+                if value.is_zero() {
+                    continue;
+                }
+                let ratio = value / dp.eval(z[k]);
+                let repulsion: ComplexNumber = (0..n).filter(|&j| j != k).map(|j| 1.0 / (z[k] - z[j])).sum();
+                let step = ratio / (1.0 - ratio * repulsion);
+                if !step.Re.is_finite() || !step.Im.is_finite() {
+                    continue;
+                }
+                z[k] -= step;
+                if step.abs() > 4.0 * f64::EPSILON * z[k].abs().max(1.0) {
+                    converged = false;
+                }
+            }
+            if converged {
+                break;
+            }
+        }
+        roots.extend(z);
+        roots
+    }
+}
+
+impl Polynomial<f64> {
+    //real roots only, those whose imaginary part vanishes to within tolerance
+    pub fn real_roots(&self, tolerance: f64) -> Vec<f64> {
+        self.roots().into_iter().filter(|z| z.Im.abs() <= tolerance).map(|z| z.Re).collect()
+    }
+}
+
+impl<T: Ring> Add<Polynomial<T>> for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn add(self, other: Polynomial<T>) -> Polynomial<T> {
+        &self + &other
+    }
+}
+
+impl<'a, T: Ring> Add<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn add(self, other: &'a Polynomial<T>) -> Polynomial<T> {
+        let n = self.coeffs.len().max(other.coeffs.len());
+        let at = |p: &Polynomial<T>, k: usize| p.coeffs.get(k).copied().unwrap_or(T::zero());
+        Polynomial::new((0..n).map(|k| at(self, k) + at(other, k)).collect())
+    }
+}
+
+impl<T: Ring> Sub<Polynomial<T>> for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn sub(self, other: Polynomial<T>) -> Polynomial<T> {
+        &self - &other
+    }
+}
+
+impl<'a, T: Ring> Sub<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn sub(self, other: &'a Polynomial<T>) -> Polynomial<T> {
+        let n = self.coeffs.len().max(other.coeffs.len());
+        let at = |p: &Polynomial<T>, k: usize| p.coeffs.get(k).copied().unwrap_or(T::zero());
+        Polynomial::new((0..n).map(|k| at(self, k) - at(other, k)).collect())
+    }
+}
+
+impl<T: Ring> Mul<Polynomial<T>> for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, other: Polynomial<T>) -> Polynomial<T> {
+        &self * &other
+    }
+}
+
+impl<'a, T: Ring> Mul<&'a Polynomial<T>> for &'a Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, other: &'a Polynomial<T>) -> Polynomial<T> {
+        if self.is_zero() || other.is_zero() {
+            return Polynomial::zero();
+        }
+        let mut coeffs = vec![T::zero(); self.coeffs.len() + other.coeffs.len() - 1];
+        for (x, &a) in self.coeffs.iter().enumerate() {
+            for (y, &b) in other.coeffs.iter().enumerate() {
+                coeffs[x + y] = coeffs[x + y] + a * b;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<T: Ring> Mul<T> for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn mul(self, other: T) -> Polynomial<T> {
+        self.apply(|c| c * other)
+    }
+}
+
+impl<T: Ring + Neg<Output = T>> Neg for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn neg(self) -> Polynomial<T> {
+        self.apply(|c| -c)
+    }
+}
+
+impl<T: Field> Div<Polynomial<T>> for Polynomial<T> {
+    type Output = Polynomial<T>;
+    //the quotient of div_rem
+    fn div(self, other: Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(&other).0
+    }
+}
+
+impl<T: Field> Rem<Polynomial<T>> for Polynomial<T> {
+    type Output = Polynomial<T>;
+    fn rem(self, other: Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(&other).1
+    }
+}
+
+impl<T: Clone> Clone for Polynomial<T> {
+    fn clone(&self) -> Self {
+        Polynomial {
+            coeffs: self.coeffs.clone()
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for Polynomial<T> {
+    fn eq(&self, other: &Polynomial<T>) -> bool {
+        self.coeffs == other.coeffs
+    }
+}
+
+impl<T: Ring> Default for Polynomial<T> {
+    fn default() -> Self {
+        Polynomial::zero()
+    }
+}
+
+//highest power first, like 2x^2 - 3x + 1, coefficients with a sign inside them are bracketed
+impl<T: Ring + Display> Display for Polynomial<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut text = String::new();
+        for (k, c) in self.coeffs.iter().enumerate().rev() {
+            if c.is_zero() {
+                continue;
+            }
+            let coefficient = c.to_string();
+            let bracketed = coefficient.chars().skip(1).any(|x| x == '+' || x == '-');
+            let (negative, body) = match coefficient.strip_prefix('-') {
+                Some(rest) if !bracketed => (true, rest.to_string()),
+                _ if bracketed => (false, format!("({})", coefficient)),
+                _ => (false, coefficient)
+            };
+            let body = if k > 0 && body == "1" {String::new()} else {body};
+            let power = match k {
+                0 => String::new(),
+                1 => "x".to_string(),
+                _ => format!("x^{}", k)
+            };
+            text = match (text.is_empty(), negative) {
+                (true, false) => format!("{}{}", body, power),
+                (true, true) => format!("-{}{}", body, power),
+                (false, false) => format!("{} + {}{}", text, body, power),
+                (false, true) => format!("{} - {}{}", text, body, power)
+            };
+        }
+        write!(f, "{}", text)
+    }
+}
+
+impl<T: Ring + Display> Debug for Polynomial<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "{}", self)
+    }
+}
+//POLYNOMIAL END
@@ -0,0 +1,973 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use core::ops::Add;
+use core::ops::Sub;
+use std::ops::Mul;
+use std::ops::Div;
+use std::fmt::Debug;
+use std::ops::AddAssign;
+use std::ops::SubAssign;
+use std::ops::MulAssign;
+use std::ops::DivAssign;
+use std::iter::Sum;
+use std::iter::Product;
+use std::ops::Neg;
+use std::str::FromStr;
+use crate::traits::*;
+use crate::parse::*;
+use crate::complex::principal_angle;
+use crate::matrix::Matrice;
+
+//QUATERNIONS START
+pub struct Quaternion<T = f64> {
+    pub Re: T,
+    pub Im: T,
+    pub Jm: T,
+    pub Km: T,
+}
+
+#[macro_export]
+macro_rules! quaternion {
+    ($ex1:expr, $ex2:expr, $ex3:expr, $ex4:expr) => {
+        $crate::quaternion::Quaternion::<f64> {
+            Re: $ex1 as f64,
+            Im: $ex2 as f64,
+            Jm: $ex3 as f64,
+            Km: $ex4 as f64,
+        }
+    };
+
+    ($ex1:expr, $ex2:expr, $ex3:expr, $ex4:expr; $t:ty) => {
+        $crate::quaternion::Quaternion::<$t> {
+            Re: $ex1 as $t,
+            Im: $ex2 as $t,
+            Jm: $ex3 as $t,
+            Km: $ex4 as $t,
+        }
+    };
+}
+
+impl<T: Real> Add<Quaternion<T>> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn add(self, other: Quaternion<T>) -> Quaternion<T> {
+        Quaternion {
+            Re: self.Re + other.Re,
+            Im: self.Im + other.Im,
+            Jm: self.Jm + other.Jm,
+            Km: self.Km + other.Km,
+        }
+    }
+}
+
+impl<T: Real> Add<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn add(self, other:T) -> Quaternion<T> {
+        Quaternion {
+            Re: self.Re + other,
+            Im: self.Im,
+            Jm: self.Jm,
+            Km: self.Km,
+        }
+    }
+}
+
+impl Add<Quaternion> for f64 {
+    type Output = Quaternion;
+    fn add(self, other:Quaternion) -> Quaternion {
+        Quaternion {
+            Re: other.Re + self,
+            Im: other.Im,
+            Jm: other.Jm,
+            Km: other.Km,
+        }
+    }
+}
+
+impl Add<Quaternion<f32>> for f32 {
+    type Output = Quaternion<f32>;
+    fn add(self, other:Quaternion<f32>) -> Quaternion<f32> {
+        Quaternion {
+            Re: other.Re + self,
+            Im: other.Im,
+            Jm: other.Jm,
+            Km: other.Km,
+        }
+    }
+}
+
+impl<T: Real> AddAssign for Quaternion<T> {
+
+    fn add_assign(&mut self, other: Quaternion<T>) {
+        *self = Quaternion {
+            Re: self.Re + other.Re,
+            Im: self.Im + other.Im,
+            Jm: self.Jm + other.Jm,
+            Km: self.Km + other.Km,
+        };
+    }
+}
+
+impl<T: Real> Sub<Quaternion<T>> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn sub(self, other: Quaternion<T>) -> Quaternion<T> {
+        Quaternion {
+            Re: self.Re - other.Re,
+            Im: self.Im - other.Im,
+            Jm: self.Jm - other.Jm,
+            Km: self.Km - other.Km,
+        }
+    }
+}
+
+impl<T: Real> Mul<Quaternion<T>> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn mul(self, other: Quaternion<T>) -> Quaternion<T> {
+        Quaternion {
+            Re: self.Re * other.Re - self.Im * other.Im - self.Jm * other.Jm - self.Km * other.Km,
+            Im: self.Re * other.Im + self.Im * other.Re + self.Jm * other.Km - self.Km * other.Jm,
+            Jm: self.Re * other.Jm - self.Im * other.Km + self.Jm * other.Re + self.Km * other.Im,
+            Km: self.Re * other.Km + self.Im * other.Jm - self.Jm * other.Im + self.Km * other.Re
+        }
+    }
+}
+
+impl<T: Real> Div<Quaternion<T>> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    //right division, a / b is a * b^-1, see div_left for b^-1 * a
+    fn div(self, other: Quaternion<T>) -> Quaternion<T> {
+        self.div_right(other)
+    }
+}
+
+impl<T: Real> Neg for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn neg(self) -> Quaternion<T> {
+        self.apply(|x| {-x})
+    }
+}
+
+impl<T: Real> Sub<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn sub(self, other: T) -> Quaternion<T> {
+        Quaternion {
+            Re: self.Re - other,
+            ..self
+        }
+    }
+}
+
+impl<T: Real> Mul<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn mul(self, other: T) -> Quaternion<T> {
+        self.apply(|x| {x * other})
+    }
+}
+
+impl<T: Real> Div<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn div(self, other: T) -> Quaternion<T> {
+        self.apply(|x| {x / other})
+    }
+}
+
+impl Sub<Quaternion> for f64 {
+    type Output = Quaternion;
+    fn sub(self, other: Quaternion) -> Quaternion {
+        -other + self
+    }
+}
+
+impl Mul<Quaternion> for f64 {
+    type Output = Quaternion;
+    fn mul(self, other: Quaternion) -> Quaternion {
+        other.apply(|x| {self * x})
+    }
+}
+
+impl Div<Quaternion> for f64 {
+    type Output = Quaternion;
+    fn div(self, other: Quaternion) -> Quaternion {
+        other.inverse().apply(|x| {self * x})
+    }
+}
+
+impl Sub<Quaternion<f32>> for f32 {
+    type Output = Quaternion<f32>;
+    fn sub(self, other: Quaternion<f32>) -> Quaternion<f32> {
+        -other + self
+    }
+}
+
+impl Mul<Quaternion<f32>> for f32 {
+    type Output = Quaternion<f32>;
+    fn mul(self, other: Quaternion<f32>) -> Quaternion<f32> {
+        other.apply(|x| {self * x})
+    }
+}
+
+impl Div<Quaternion<f32>> for f32 {
+    type Output = Quaternion<f32>;
+    fn div(self, other: Quaternion<f32>) -> Quaternion<f32> {
+        other.inverse().apply(|x| {self * x})
+    }
+}
+
+impl<T: Real> AddAssign<T> for Quaternion<T> {
+    fn add_assign(&mut self, other: T) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Real> SubAssign for Quaternion<T> {
+    fn sub_assign(&mut self, other: Quaternion<T>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Real> SubAssign<T> for Quaternion<T> {
+    fn sub_assign(&mut self, other: T) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Real> MulAssign for Quaternion<T> {
+    //right multiplication, a *= b is a = a * b
+    fn mul_assign(&mut self, other: Quaternion<T>) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Real> MulAssign<T> for Quaternion<T> {
+    fn mul_assign(&mut self, other: T) {
+        *self = *self * other;
+    }
+}
+
+impl<T: Real> DivAssign for Quaternion<T> {
+    fn div_assign(&mut self, other: Quaternion<T>) {
+        *self = *self / other;
+    }
+}
+
+impl<T: Real> DivAssign<T> for Quaternion<T> {
+    fn div_assign(&mut self, other: T) {
+        *self = *self / other;
+    }
+}
+
+forward_ref_binop!([T: Real] Add, add for Quaternion<T>);
+forward_ref_binop!([T: Real] Sub, sub for Quaternion<T>);
+forward_ref_binop!([T: Real] Mul, mul for Quaternion<T>);
+forward_ref_binop!([T: Real] Div, div for Quaternion<T>);
+forward_ref_binop!([T: Real] Add, add for Quaternion<T>, T => Quaternion<T>);
+forward_ref_binop!([T: Real] Sub, sub for Quaternion<T>, T => Quaternion<T>);
+forward_ref_binop!([T: Real] Mul, mul for Quaternion<T>, T => Quaternion<T>);
+forward_ref_binop!([T: Real] Div, div for Quaternion<T>, T => Quaternion<T>);
+forward_ref_binop!([] Add, add for f64, Quaternion => Quaternion);
+forward_ref_binop!([] Sub, sub for f64, Quaternion => Quaternion);
+forward_ref_binop!([] Mul, mul for f64, Quaternion => Quaternion);
+forward_ref_binop!([] Div, div for f64, Quaternion => Quaternion);
+forward_ref_binop!([] Add, add for f32, Quaternion<f32> => Quaternion<f32>);
+forward_ref_binop!([] Sub, sub for f32, Quaternion<f32> => Quaternion<f32>);
+forward_ref_binop!([] Mul, mul for f32, Quaternion<f32> => Quaternion<f32>);
+forward_ref_binop!([] Div, div for f32, Quaternion<f32> => Quaternion<f32>);
+forward_ref_unop!([T: Real] Neg, neg for Quaternion<T>);
+forward_ref_op_assign!([T: Real] AddAssign, add_assign for Quaternion<T>, Quaternion<T>);
+forward_ref_op_assign!([T: Real] SubAssign, sub_assign for Quaternion<T>, Quaternion<T>);
+forward_ref_op_assign!([T: Real] MulAssign, mul_assign for Quaternion<T>, Quaternion<T>);
+forward_ref_op_assign!([T: Real] DivAssign, div_assign for Quaternion<T>, Quaternion<T>);
+forward_ref_op_assign!([T: Real] AddAssign, add_assign for Quaternion<T>, T);
+forward_ref_op_assign!([T: Real] SubAssign, sub_assign for Quaternion<T>, T);
+forward_ref_op_assign!([T: Real] MulAssign, mul_assign for Quaternion<T>, T);
+forward_ref_op_assign!([T: Real] DivAssign, div_assign for Quaternion<T>, T);
+
+impl<T: Real> Sum for Quaternion<T> {
+    fn sum<I: Iterator<Item = Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.fold(Quaternion::zero(), |a, b| a + b)
+    }
+}
+
+impl<'a, T: Real> Sum<&'a Quaternion<T>> for Quaternion<T> {
+    fn sum<I: Iterator<Item = &'a Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.fold(Quaternion::zero(), |a, b| a + *b)
+    }
+}
+
+impl<T: Real> Product for Quaternion<T> {
+    //multiplies left to right in iteration order
+    fn product<I: Iterator<Item = Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.fold(Quaternion::one(), |a, b| a * b)
+    }
+}
+
+impl<'a, T: Real> Product<&'a Quaternion<T>> for Quaternion<T> {
+    fn product<I: Iterator<Item = &'a Quaternion<T>>>(iter: I) -> Quaternion<T> {
+        iter.fold(Quaternion::one(), |a, b| a * *b)
+    }
+}
+
+impl<T: Copy> Copy for Quaternion<T> {}
+
+impl<T: Copy> Clone for Quaternion<T> {
+    fn clone(&self) -> Quaternion<T> {
+        *self
+    }
+}
+
+impl<T: Real> Default for Quaternion<T> {
+    fn default() -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Quaternion {
+            Re: zero,
+            Im: one,
+            Jm: one,
+            Km: one
+        }
+    }
+}
+
+impl<T: Real> Ring for Quaternion<T> {
+    fn zero() -> Self {
+        Quaternion::zero()
+    }
+
+    fn one() -> Self {
+        Quaternion::one()
+    }
+}
+
+impl<T: Real> DivisionRing for Quaternion<T> {
+    type Real = T;
+
+    fn recip(self) -> Self {
+        self.inverse()
+    }
+
+    fn conj(self) -> Self {
+        self.conjugate()
+    }
+
+    fn norm_sqr(self) -> T {
+        self.dot(&self)
+    }
+
+    fn map_parts(self, f: impl Fn(T) -> T) -> Self {
+        self.apply(f)
+    }
+
+    fn abs(self) -> T {
+        self.norm()
+    }
+}
+
+impl<T: Real + Display> Display for Quaternion<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (zero, one) = (T::zero(), T::one());
+        let absolute = |x: T| {if x >= zero {x} else {-x}};
+        let mut text: String = String::new();
+        if self.Re != zero || (self.Im == zero && self.Jm == zero && self.Km == zero) {
+            text = format!("{}", self.Re);
+        }
+        if self.Im != zero {
+            if self.Im == one || self.Im == -one  {
+                text = format!("{}{}i", text,
+                        if self.Im == absolute(self.Im) {
+                            if self.Re != zero {"+"}else {""}}
+                        else {"-"});
+            }
+            else {
+                text = format!("{}{}{}i", text,
+                        if self.Im == absolute(self.Im) {
+                            if text.is_empty() {""} else {"+"}}
+                        else {"-"},
+                        absolute(self.Im));
+            }
+        }
+        if self.Jm != zero {
+            if self.Jm == one || self.Jm == -one {
+                text = format!("{}{}j", text,
+                        if self.Jm == absolute(self.Jm) {
+                            if self.Im != zero || self.Re != zero {"+"}else {""}}
+                        else {"-"});
+            }
+            else {
+                text = format!("{}{}{}j", text,
+                        if self.Jm == absolute(self.Jm) {
+                            if text.is_empty() {""} else {"+"}}
+                        else {"-"},
+                        absolute(self.Jm));
+            }
+        }
+        if self.Km != zero {
+            if self.Km == one || self.Km == -one {
+                text = format!("{}{}k", text,
+                        if self.Km == absolute(self.Km) {
+                            if self.Im != zero || self.Re != zero || self.Jm != zero {"+"}else {""}}
+                        else {"-"});
+            }
+            else {
+                text = format!("{}{}{}k", text,
+                        if self.Km == absolute(self.Km) {
+                            if text.is_empty() {""} else {"+"}}
+                        else {"-"},
+                        absolute(self.Km));
+            }
+        }
+        write!(f, "{}", text)
+    }
+}
+
+
+impl<T: Real + Display> Debug for Quaternion<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let (zero, one) = (T::zero(), T::one());
+        let absolute = |x: T| {if x >= zero {x} else {-x}};
+        let mut text: String = String::new();
+        if self.Re != zero || (self.Im == zero && self.Jm == zero && self.Km == zero) {
+            text = format!("{}", self.Re);
+        }
+        if self.Im != zero {
+            if self.Im == one || self.Im == -one  {
+                text = format!("{}{}i", text,
+                        if self.Im == absolute(self.Im) {
+                            if self.Re != zero {"+"}else {""}}
+                        else {"-"});
+            }
+            else {
+                text = format!("{}{}{}i", text,
+                        if self.Im == absolute(self.Im) {
+                            if text.is_empty() {""} else {"+"}}
+                        else {"-"},
+                        absolute(self.Im));
+            }
+        }
+        if self.Jm != zero {
+            if self.Jm == one || self.Jm == -one {
+                text = format!("{}{}j", text,
+                        if self.Jm == absolute(self.Jm) {
+                            if self.Im != zero || self.Re != zero {"+"}else {""}}
+                        else {"-"});
+            }
+            else {
+                text = format!("{}{}{}j", text,
+                        if self.Jm == absolute(self.Jm) {
+                            if text.is_empty() {""} else {"+"}}
+                        else {"-"},
+                        absolute(self.Jm));
+            }
+        }
+        if self.Km != zero {
+            if self.Km == one || self.Km == -one {
+                text = format!("{}{}k", text,
+                        if self.Km == absolute(self.Km) {
+                            if self.Im != zero || self.Re != zero || self.Jm != zero {"+"}else {""}}
+                        else {"-"});
+            }
+            else {
+                text = format!("{}{}{}k", text,
+                        if self.Km == absolute(self.Km) {
+                            if text.is_empty() {""} else {"+"}}
+                        else {"-"},
+                        absolute(self.Km));
+            }
+        }
+        write!(f, "{}", text)
+    }
+}
+
+impl<T: Real + FromStr> FromStr for Quaternion<T> {
+    type Err = ParseNumberError;
+    fn from_str(s: &str) -> std::result::Result<Quaternion<T>, ParseNumberError> {
+        let parts = parse_parts::<T>(s, &[('i', 1), ('j', 2), ('k', 3)])?;
+        Ok(Quaternion::from_parts(parts[0], [parts[1], parts[2], parts[3]]))
+    }
+}
+
+impl<T: PartialEq> PartialEq for Quaternion<T> {
+    fn eq(&self, other: &Quaternion<T>) -> bool {
+        (self.Re == other.Re)&&(self.Im == other.Im)&&(self.Jm==other.Jm)&&(self.Km==other.Km)
+    }
+}
+
+impl<T: Real> Vectorable for Quaternion<T> {
+    fn vectorify(&self) -> Vec<f64> {
+        vec![self.Re.to_f64(), self.Im.to_f64(), self.Jm.to_f64(), self.Km.to_f64()]
+    }
+}
+
+impl From<Quaternion<f32>> for Quaternion<f64> {
+    //every f32 is exactly an f64, nothing is lost
+    fn from(q: Quaternion<f32>) -> Quaternion<f64> {
+        quaternion!(q.Re, q.Im, q.Jm, q.Km)
+    }
+}
+
+impl Quaternion<f64> {
+    pub fn to_f32(self) -> Quaternion<f32> {
+        //rounds each component to the nearest f32
+        quaternion!(self.Re, self.Im, self.Jm, self.Km; f32)
+    }
+}
+
+impl<T: Real> Quaternion<T> {
+    pub fn zero() -> Self {
+        let zero = T::zero();
+        Quaternion {Re: zero, Im: zero, Jm: zero, Km: zero}
+    }
+
+    pub fn one() -> Self {
+        Quaternion {Re: T::one(), ..Self::zero()}
+    }
+
+    pub fn newi() -> Self {
+        Quaternion {Im: T::one(), ..Self::zero()}
+    }
+
+    pub fn newj() -> Self {
+        Quaternion {Jm: T::one(), ..Self::zero()}
+    }
+
+    pub fn newk() -> Self {
+        Quaternion {Km: T::one(), ..Self::zero()}
+    }
+
+    pub fn from_parts(re: T, v: [T; 3]) -> Self {
+        Quaternion {
+            Re: re,
+            Im: v[0],
+            Jm: v[1],
+            Km: v[2]
+        }
+    }
+
+    pub fn cast<U: Real>(self) -> Quaternion<U> {
+        //converts through f64, exact when U is at least as precise as T
+        Quaternion {
+            Re: U::from_f64(self.Re.to_f64()),
+            Im: U::from_f64(self.Im.to_f64()),
+            Jm: U::from_f64(self.Jm.to_f64()),
+            Km: U::from_f64(self.Km.to_f64())
+        }
+    }
+
+    pub fn real(&self) -> bool {
+        let zero = T::zero();
+        !(self.Im != zero || self.Jm != zero || self.Km != zero)
+    }
+
+    pub fn norm(&self) -> T {
+        self.dot(self).sqrt()
+    }
+
+    pub fn apply(&self, f: impl Fn(T) -> T) -> Self {
+        Self {
+            Re: f(self.Re),
+            Im: f(self.Im),
+            Jm: f(self.Jm),
+            Km: f(self.Km)
+        }
+    }
+
+    pub fn conjugate(&self) -> Self {
+        let half = T::from_f64(-0.5);
+        ((*self) + Quaternion::newi()*(*self)*Quaternion::newi() +
+            Quaternion::newj()*(*self)*Quaternion::newj() +
+            Quaternion::newk()*(*self)*Quaternion::newk()).apply(|x|{x*half})
+    }
+
+    pub fn inverse(&self) -> Self {
+        let norm_sqr = self.dot(self);
+        self.conjugate().apply(
+            |x| {x/norm_sqr}
+        )
+    }
+
+    //None for the zero quaternion, where inverse() would divide by zero
+    pub fn checked_inverse(&self) -> Option<Self> {
+        let norm_sqr = self.dot(self);
+        if norm_sqr == T::zero() {
+            None
+        } else {
+            Some(self.conjugate().apply(|x| {x/norm_sqr}))
+        }
+    }
+
+    //multiplication does not commute, so there are two quotients:
+    //div_right(b) is self * b^-1 and div_left(b) is b^-1 * self
+    pub fn div_right(&self, other: Self) -> Self {
+        (*self) * other.inverse()
+    }
+
+    pub fn div_left(&self, other: Self) -> Self {
+        other.inverse() * (*self)
+    }
+
+    //None when dividing by the zero quaternion
+    pub fn checked_div_right(&self, other: Self) -> Option<Self> {
+        Some((*self) * other.checked_inverse()?)
+    }
+
+    pub fn checked_div_left(&self, other: Self) -> Option<Self> {
+        Some(other.checked_inverse()? * (*self))
+    }
+
+    pub fn unit_of(&self) -> Self {
+        let norm = self.norm();
+        self.apply(
+            |x| {x/norm}
+        )
+    }
+
+    pub fn exp(&self) -> Self {
+        //e^(a + v) = e^a (cos|v| + v/|v| sin|v|)
+        let len = norm3([self.Im, self.Jm, self.Km]);
+        if len == T::zero() {
+            return Quaternion {Re: self.Re.exp(), ..Self::zero()};
+        }
+        let s = self.Re.exp() * len.sin() / len;
+        Quaternion::from_parts(self.Re.exp() * len.cos(), [self.Im * s, self.Jm * s, self.Km * s])
+    }
+
+    pub fn ln(&self) -> Self {
+        //ln(a + v) = ln|q| + v/|v| atan2(|v|, a), the vector part has length in [0, pi]
+        //a negative real has no preferred axis, i is used (so ln(-1) = pi i, as for complex numbers)
+        let len = norm3([self.Im, self.Jm, self.Km]);
+        if len == T::zero() {
+            if self.Re < T::zero() {
+                return Quaternion {Re: (-self.Re).ln(), Im: T::pi(), ..Self::zero()};
+            }
+            return Quaternion {Re: self.Re.ln(), ..Self::zero()};
+        }
+        let s = len.atan2(self.Re) / len;
+        Quaternion::from_parts(self.norm().ln(), [self.Im * s, self.Jm * s, self.Km * s])
+    }
+
+    pub fn powf(&self, x: T) -> Self {
+        //q^x = exp(x ln q)
+        let zero = T::zero();
+        if self.norm() == zero {
+            return if x == zero {Quaternion::one()}
+                else if x > zero {Quaternion::zero()}
+                else {Quaternion {Re: T::infinity(), ..Self::zero()}};
+        }
+        self.ln().apply(|c| {c * x}).exp()
+    }
+
+    pub fn pow(&self, p: Self) -> Self {
+        //q^p = exp(ln(q) p), p multiplied on the right since the product does not commute
+        let zero = T::zero();
+        if self.norm() == zero {
+            return if p.norm() == zero {Quaternion::one()}
+                else if p.real() && p.Re > zero {Quaternion::zero()}
+                else {Quaternion::zero().apply(|_| {T::nan()})};
+        }
+        (self.ln() * p).exp()
+    }
+
+    //ROTATIONS
+    //unit quaternions are used as rotations, v -> q v q*
+
+    pub fn from_axis_angle(axis: [T; 3], angle: T) -> Self {
+        let len = norm3(axis);
+        if len == T::zero() {
+            return Quaternion::one();
+        }
+        let half = angle / T::from_f64(2.0);
+        let s = half.sin() / len;
+        Quaternion::from_parts(half.cos(), [axis[0] * s, axis[1] * s, axis[2] * s])
+    }
+
+    pub fn to_axis_angle(self) -> ([T; 3], T) {
+        //angle in [0, 2pi], the axis is arbitrary when the angle is zero
+        let (zero, one) = (T::zero(), T::one());
+        let q = self.unit_of();
+        let len = norm3([q.Im, q.Jm, q.Km]);
+        if len == zero {
+            return ([one, zero, zero], zero);
+        }
+        ([q.Im / len, q.Jm / len, q.Km / len], T::from_f64(2.0) * len.atan2(q.Re))
+    }
+
+    pub fn rotate_vector(&self, v: [T; 3]) -> [T; 3] {
+        let q = self.unit_of();
+        let r = q * Quaternion::from_parts(T::zero(), v) * q.conjugate();
+        [r.Im, r.Jm, r.Km]
+    }
+
+    pub fn to_rotation_matrix(self) -> Matrice<T> {
+        let (one, two) = (T::one(), T::from_f64(2.0));
+        let q = self.unit_of();
+        let (w, x, y, z) = (q.Re, q.Im, q.Jm, q.Km);
+        Matrice::new(3, 3, vec![
+            vec![one - two*(y*y + z*z), two*(x*y - w*z), two*(x*z + w*y)],
+            vec![two*(x*y + w*z), one - two*(x*x + z*z), two*(y*z - w*x)],
+            vec![two*(x*z - w*y), two*(y*z + w*x), one - two*(x*x + y*y)]
+        ])
+    }
+
+    pub fn from_rotation_matrix(m: &Matrice<T>) -> Self {
+        //Shepperd's method: divide by the largest of the four candidate
+        //components so the result stays accurate for every rotation
+        assert!(m.shape() == (3, 3), "a rotation matrix is 3x3, got {}x{}", m.height, m.width);
+        let (one, half, four) = (T::one(), T::from_f64(0.5), T::from_f64(4.0));
+        let d = |x: usize, y: usize| m[(x, y)];
+        let trace = d(0, 0) + d(1, 1) + d(2, 2);
+        let q = if trace >= d(0, 0) && trace >= d(1, 1) && trace >= d(2, 2) {
+            let w = half * (one + trace).sqrt();
+            Quaternion::from_parts(w, [(d(2, 1) - d(1, 2)) / (four*w), (d(0, 2) - d(2, 0)) / (four*w), (d(1, 0) - d(0, 1)) / (four*w)])
+        } else if d(0, 0) >= d(1, 1) && d(0, 0) >= d(2, 2) {
+            let x = half * (one + d(0, 0) - d(1, 1) - d(2, 2)).sqrt();
+            Quaternion::from_parts((d(2, 1) - d(1, 2)) / (four*x), [x, (d(0, 1) + d(1, 0)) / (four*x), (d(0, 2) + d(2, 0)) / (four*x)])
+        } else if d(1, 1) >= d(2, 2) {
+            let y = half * (one - d(0, 0) + d(1, 1) - d(2, 2)).sqrt();
+            Quaternion::from_parts((d(0, 2) - d(2, 0)) / (four*y), [(d(0, 1) + d(1, 0)) / (four*y), y, (d(1, 2) + d(2, 1)) / (four*y)])
+        } else {
+            let z = half * (one - d(0, 0) - d(1, 1) + d(2, 2)).sqrt();
+            Quaternion::from_parts((d(1, 0) - d(0, 1)) / (four*z), [(d(0, 2) + d(2, 0)) / (four*z), (d(1, 2) + d(2, 1)) / (four*z), z])
+        };
+        //q and -q are the same rotation, keep the one with Re >= 0
+        if q.Re < T::zero() {-q} else {q}
+    }
+
+    pub fn from_two_vectors(from: [T; 3], to: [T; 3]) -> Self {
+        //shortest arc rotation taking the direction of from onto the direction of to
+        let (zero, one) = (T::zero(), T::one());
+        let (a, b) = (norm3(from), norm3(to));
+        if a == zero || b == zero {
+            return Quaternion::one();
+        }
+        let u = [from[0] / a, from[1] / a, from[2] / a];
+        let v = [to[0] / b, to[1] / b, to[2] / b];
+        let d = dot3(u, v);
+        if d < -one + T::epsilon() * T::from_f64(4096.0) {
+            //opposite vectors, turn half way around any axis orthogonal to from
+            let mut axis = cross3([one, zero, zero], u);
+            if norm3(axis) < T::from_f64(1e-6) {
+                axis = cross3([zero, one, zero], u);
+            }
+            return Quaternion::from_axis_angle(axis, T::pi());
+        }
+        let c = cross3(u, v);
+        Quaternion::from_parts(one + d, c).unit_of()
+    }
+
+    pub fn from_euler(seq: EulerSequence, a: T, b: T, c: T) -> Self {
+        //a, b and c are the angles about the first, second and third axis of the sequence
+        let [i, j, k] = seq.axes().indices();
+        let about = |axis: usize, angle: T| {
+            let mut v = [T::zero(); 3];
+            v[axis] = T::one();
+            Quaternion::from_axis_angle(v, angle)
+        };
+        match seq {
+            EulerSequence::Intrinsic(_) => about(i, a) * about(j, b) * about(k, c),
+            EulerSequence::Extrinsic(_) => about(k, c) * about(j, b) * about(i, a)
+        }
+    }
+
+    pub fn to_euler(self, seq: EulerSequence) -> [T; 3] {
+        //Bernardes and Viollet's direct method, worked out for the intrinsic
+        //sequence (an extrinsic sequence is the reversed intrinsic one)
+        //all angles are in (-pi, pi], the middle one is in [0, pi] for proper
+        //Euler sequences and in [-pi/2, pi/2] for Tait-Bryan ones
+        //in gimbal lock only the sum (or difference) of the outer angles is
+        //defined, the third angle of the sequence is then set to zero
+        let (pi, two) = (T::pi(), T::from_f64(2.0));
+        //1e-7 for f64, looser for f32 where the middle angle is only good to ~1e-4
+        let lock = T::from_f64(1e-7).max(T::epsilon() * T::from_f64(1000.0));
+        let extrinsic = matches!(seq, EulerSequence::Extrinsic(_));
+        let [mut i, j, mut k] = seq.axes().indices();
+        if extrinsic {
+            std::mem::swap(&mut i, &mut k);
+        }
+        let symmetric = i == k;
+        if symmetric {
+            k = 3 - i - j;
+        }
+        let sign = T::from_f64(((i as i32 - j as i32) * (j as i32 - k as i32) * (k as i32 - i as i32) / 2) as f64);
+
+        let q = self.unit_of();
+        let v = [q.Im, q.Jm, q.Km];
+        let (a, b, c, d) = if symmetric {
+            (q.Re, v[i], v[j], v[k] * sign)
+        } else {
+            (q.Re - v[j], v[i] - v[k] * sign, q.Re + v[j], v[i] + v[k] * sign)
+        };
+
+        let mut angles = [T::zero(); 3];
+        angles[1] = two * c.hypot(d).atan2(a.hypot(b));
+        let half_sum = b.atan2(a);
+        let half_diff = d.atan2(c);
+        let locked_at_zero = angles[1].copysign(T::one()) < lock;
+        let locked_at_pi = (angles[1] - pi).copysign(T::one()) < lock;
+        if !locked_at_zero && !locked_at_pi {
+            angles[0] = half_sum + half_diff;
+            angles[2] = half_sum - half_diff;
+        } else if extrinsic {
+            angles[2] = if locked_at_zero {two * half_sum} else {-two * half_diff};
+        } else {
+            angles[0] = if locked_at_zero {two * half_sum} else {two * half_diff};
+        }
+
+        if !symmetric {
+            angles[2] = angles[2] * -sign;
+            angles[1] = angles[1] - pi / two;
+        }
+        if extrinsic {
+            angles.swap(0, 2);
+        }
+        angles.map(principal_angle)
+    }
+
+    //INTERPOLATION
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.Re*other.Re + self.Im*other.Im + self.Jm*other.Jm + self.Km*other.Km
+    }
+
+    pub fn nlerp(a: Self, b: Self, t: T) -> Self {
+        //linear blend renormalized, cheap but not constant speed
+        let b = if a.dot(&b) < T::zero() {-b} else {b};
+        (a.apply(|x| {x * (T::one() - t)}) + b.apply(|x| {x * t})).unit_of()
+    }
+
+    pub fn slerp(a: Self, b: Self, t: T) -> Self {
+        //constant speed along the shorter of the two arcs between a and b
+        let (a, b) = (a.unit_of(), b.unit_of());
+        if a.dot(&b) < T::zero() {
+            return slerp_arc(a, -b, t);
+        }
+        slerp_arc(a, b, t)
+    }
+
+    pub fn squad(q0: Self, q1: Self, s0: Self, s1: Self, t: T) -> Self {
+        //spherical cubic between q0 and q1 with inner control points s0 and s1
+        //the inner blends must not flip signs or the curve jumps half way through
+        slerp_arc(
+            slerp_arc(q0.unit_of(), q1.unit_of(), t),
+            slerp_arc(s0.unit_of(), s1.unit_of(), t),
+            T::from_f64(2.0) * t * (T::one() - t))
+    }
+
+    pub fn squad_control_point(prev: Self, cur: Self, next: Self) -> Self {
+        //s = q exp(-(ln(q^-1 next) + ln(q^-1 prev)) / 4)
+        let inv = cur.inverse();
+        let sum = (inv * next).ln() + (inv * prev).ln();
+        let quarter = T::from_f64(-0.25);
+        cur * sum.apply(|x| {x * quarter}).exp()
+    }
+
+    pub fn squad_path(keys: &[Self], u: T) -> Self {
+        //smooth interpolation through keys, u runs from 0 (first key) to keys.len() - 1 (last key)
+        if keys.is_empty() {
+            return Quaternion::one();
+        }
+        //line the keys up on the same hemisphere so each segment takes the short way
+        let mut aligned: Vec<Quaternion<T>> = vec![keys[0].unit_of()];
+        for key in &keys[1..] {
+            let key = key.unit_of();
+            let last = aligned[aligned.len() - 1];
+            aligned.push(if last.dot(&key) < T::zero() {-key} else {key});
+        }
+        let n = aligned.len();
+        if n == 1 {
+            return aligned[0];
+        }
+        let u = u.max(T::zero()).min(T::from_f64((n - 1) as f64));
+        let i = (u.floor().to_f64() as usize).min(n - 2);
+        let t = u - T::from_f64(i as f64);
+        let control = |k: usize| {
+            Quaternion::squad_control_point(aligned[k.saturating_sub(1)], aligned[k], aligned[(k + 1).min(n - 1)])
+        };
+        Quaternion::squad(aligned[i], aligned[i + 1], control(i), control(i + 1), t)
+    }
+
+}
+
+fn slerp_arc<T: Real>(a: Quaternion<T>, b: Quaternion<T>, t: T) -> Quaternion<T> {
+    //slerp along the arc from a to b as given, both unit
+    let one = T::one();
+    let d = a.dot(&b).max(-one).min(one);
+    if d > T::from_f64(0.9995) {
+        //sin(theta) is too small to divide by, the arc is nearly a straight line
+        return (a.apply(|x| {x * (one - t)}) + b.apply(|x| {x * t})).unit_of();
+    }
+    let theta = d.acos();
+    let (wa, wb) = (((one - t) * theta).sin() / theta.sin(), (t * theta).sin() / theta.sin());
+    a.apply(|x| {x * wa}) + b.apply(|x| {x * wb})
+}
+
+//EULER ANGLES START
+#[allow(clippy::upper_case_acronyms)]
+pub enum EulerAxes {
+    //Tait-Bryan sequences
+    XYZ, XZY, YXZ, YZX, ZXY, ZYX,
+    //proper Euler sequences
+    XYX, XZX, YXY, YZY, ZXZ, ZYZ
+}
+
+pub enum EulerSequence {
+    //rotations about the axes of the rotating body frame
+    Intrinsic(EulerAxes),
+    //rotations about the axes of the fixed world frame
+    Extrinsic(EulerAxes)
+}
+
+impl Copy for EulerAxes {}
+
+impl Clone for EulerAxes {
+    fn clone(&self) -> EulerAxes {
+        *self
+    }
+}
+
+impl Copy for EulerSequence {}
+
+impl Clone for EulerSequence {
+    fn clone(&self) -> EulerSequence {
+        *self
+    }
+}
+
+impl EulerAxes {
+    pub fn indices(&self) -> [usize; 3] {
+        match self {
+            EulerAxes::XYZ => [0, 1, 2],
+            EulerAxes::XZY => [0, 2, 1],
+            EulerAxes::YXZ => [1, 0, 2],
+            EulerAxes::YZX => [1, 2, 0],
+            EulerAxes::ZXY => [2, 0, 1],
+            EulerAxes::ZYX => [2, 1, 0],
+            EulerAxes::XYX => [0, 1, 0],
+            EulerAxes::XZX => [0, 2, 0],
+            EulerAxes::YXY => [1, 0, 1],
+            EulerAxes::YZY => [1, 2, 1],
+            EulerAxes::ZXZ => [2, 0, 2],
+            EulerAxes::ZYZ => [2, 1, 2]
+        }
+    }
+}
+
+impl EulerSequence {
+    pub fn axes(&self) -> EulerAxes {
+        match self {
+            EulerSequence::Intrinsic(axes) => *axes,
+            EulerSequence::Extrinsic(axes) => *axes
+        }
+    }
+}
+//EULER ANGLES END
+
+pub fn dot3<T: Real>(a: [T; 3], b: [T; 3]) -> T {
+    a[0]*b[0] + a[1]*b[1] + a[2]*b[2]
+}
+
+pub fn cross3<T: Real>(a: [T; 3], b: [T; 3]) -> [T; 3] {
+    [a[1]*b[2] - a[2]*b[1], a[2]*b[0] - a[0]*b[2], a[0]*b[1] - a[1]*b[0]]
+}
+
+pub fn norm3<T: Real>(a: [T; 3]) -> T {
+    dot3(a, a).sqrt()
+}
+//QUATERNIONS END
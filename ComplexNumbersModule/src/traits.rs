@@ -0,0 +1,504 @@
+use core::ops::Add;
+use core::ops::Sub;
+use std::ops::Mul;
+use std::ops::Div;
+use std::ops::Neg;
+
+pub fn absolute(x: f64) -> f64 {
+    if x >= 0.0 {x} else {-x}
+}
+
+pub fn max(x: f64, y: f64) -> usize {
+    if x >= y {x as usize} else {y as usize}
+}
+
+pub fn min(x: f64, y: f64) -> usize {
+    if x <= y {x as usize} else {y as usize}
+}
+
+
+//SOME TRAITS WE'RE GONNA NEED
+pub trait Vectorable {
+    fn vectorify(&self) -> Vec<f64>;
+}
+
+//addition, subtraction and multiplication with identities, multiplication need not commute
+//every element needs an additive inverse, so unsigned integers are not rings
+pub trait Ring: Copy + PartialEq + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+//every nonzero element is invertible, quaternions stop here
+//conj, abs and norm_sqr live at this level so pivoting works for quaternions too,
+//magnitudes come out in the real type the components are stored in
+pub trait DivisionRing: Ring {
+    type Real: Real;
+    fn recip(self) -> Self;
+    fn conj(self) -> Self;
+    fn norm_sqr(self) -> Self::Real;
+    //applies f to every real component
+    fn map_parts(self, f: impl Fn(Self::Real) -> Self::Real) -> Self;
+
+    fn abs(self) -> Self::Real {
+        self.norm_sqr().sqrt()
+    }
+
+    //self * other^-1
+    fn div_right(self, other: Self) -> Self {
+        self * other.recip()
+    }
+
+    //other^-1 * self
+    fn div_left(self, other: Self) -> Self {
+        other.recip() * self
+    }
+}
+
+//commutative division ring, so a / b is unambiguous
+pub trait Field: DivisionRing + Div<Output = Self> {}
+
+//ordered field standing in for the real numbers, with the elementary functions
+pub trait Real: Field + DivisionRing<Real = Self> + PartialOrd + Neg<Output = Self> {
+    fn from_f64(x: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn pi() -> Self;
+    fn infinity() -> Self;
+    fn nan() -> Self;
+    //gap between 1 and the next representable value
+    fn epsilon() -> Self;
+    fn sqrt(self) -> Self;
+    fn cbrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    //ln(1 + self), exact for small self
+    fn ln_1p(self) -> Self;
+    fn powf(self, x: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn asinh(self) -> Self;
+    fn acos(self) -> Self;
+    fn floor(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn copysign(self, sign: Self) -> Self;
+    fn max(self, other: Self) -> Self;
+    fn min(self, other: Self) -> Self;
+
+    //NaN is the only value not equal to itself
+    #[allow(clippy::eq_op)]
+    fn is_nan(self) -> bool {
+        self != self
+    }
+
+    fn is_infinite(self) -> bool {
+        self == Self::infinity() || self == -Self::infinity()
+    }
+
+    fn is_finite(self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+}
+
+//a field over Real, split into real and imaginary parts
+pub trait ComplexField: Field {
+    fn from_real(x: Self::Real) -> Self;
+    fn re(self) -> Self::Real;
+    fn im(self) -> Self::Real;
+}
+//TRAITS END
+
+
+//SOME TRAIT IMPLEMENTATIONS
+impl Ring for i32 {
+    fn zero() -> i32 {
+        0
+    }
+
+    fn one() -> i32 {
+        1
+    }
+}
+
+impl Ring for i64 {
+    fn zero() -> i64 {
+        0
+    }
+
+    fn one() -> i64 {
+        1
+    }
+}
+
+impl Ring for f64 {
+    fn zero() -> f64 {
+        0.0
+    }
+
+    fn one() -> f64 {
+        1.0
+    }
+}
+
+impl DivisionRing for f64 {
+    type Real = f64;
+
+    fn recip(self) -> f64 {
+        1.0 / self
+    }
+
+    fn conj(self) -> f64 {
+        self
+    }
+
+    fn norm_sqr(self) -> f64 {
+        self * self
+    }
+
+    fn map_parts(self, f: impl Fn(f64) -> f64) -> f64 {
+        f(self)
+    }
+
+    fn abs(self) -> f64 {
+        self.abs()
+    }
+
+    fn div_right(self, other: f64) -> f64 {
+        self / other
+    }
+
+    fn div_left(self, other: f64) -> f64 {
+        self / other
+    }
+}
+
+impl Field for f64 {}
+
+impl Real for f64 {
+    fn from_f64(x: f64) -> f64 {
+        x
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn pi() -> f64 {
+        std::f64::consts::PI
+    }
+
+    fn infinity() -> f64 {
+        f64::INFINITY
+    }
+
+    fn nan() -> f64 {
+        f64::NAN
+    }
+
+    fn epsilon() -> f64 {
+        f64::EPSILON
+    }
+
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+
+    fn cbrt(self) -> f64 {
+        f64::cbrt(self)
+    }
+
+    fn exp(self) -> f64 {
+        f64::exp(self)
+    }
+
+    fn ln(self) -> f64 {
+        f64::ln(self)
+    }
+
+    fn ln_1p(self) -> f64 {
+        f64::ln_1p(self)
+    }
+
+    fn sin(self) -> f64 {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        f64::cos(self)
+    }
+
+    fn sinh(self) -> f64 {
+        f64::sinh(self)
+    }
+
+    fn cosh(self) -> f64 {
+        f64::cosh(self)
+    }
+
+    fn asinh(self) -> f64 {
+        f64::asinh(self)
+    }
+
+    fn acos(self) -> f64 {
+        f64::acos(self)
+    }
+
+    fn floor(self) -> f64 {
+        f64::floor(self)
+    }
+
+    fn powf(self, x: f64) -> f64 {
+        f64::powf(self, x)
+    }
+
+    fn atan2(self, x: f64) -> f64 {
+        f64::atan2(self, x)
+    }
+
+    fn hypot(self, other: f64) -> f64 {
+        f64::hypot(self, other)
+    }
+
+    fn copysign(self, sign: f64) -> f64 {
+        f64::copysign(self, sign)
+    }
+
+    fn max(self, other: f64) -> f64 {
+        f64::max(self, other)
+    }
+
+    fn min(self, other: f64) -> f64 {
+        f64::min(self, other)
+    }
+}
+
+impl ComplexField for f64 {
+    fn from_real(x: f64) -> f64 {
+        x
+    }
+
+    fn re(self) -> f64 {
+        self
+    }
+
+    fn im(self) -> f64 {
+        0.0
+    }
+}
+
+impl Ring for f32 {
+    fn zero() -> f32 {
+        0.0
+    }
+
+    fn one() -> f32 {
+        1.0
+    }
+}
+
+impl DivisionRing for f32 {
+    type Real = f32;
+
+    fn recip(self) -> f32 {
+        1.0 / self
+    }
+
+    fn conj(self) -> f32 {
+        self
+    }
+
+    fn norm_sqr(self) -> f32 {
+        self * self
+    }
+
+    fn map_parts(self, f: impl Fn(f32) -> f32) -> f32 {
+        f(self)
+    }
+
+    fn abs(self) -> f32 {
+        self.abs()
+    }
+
+    fn div_right(self, other: f32) -> f32 {
+        self / other
+    }
+
+    fn div_left(self, other: f32) -> f32 {
+        self / other
+    }
+}
+
+impl Field for f32 {}
+
+impl Real for f32 {
+    fn from_f64(x: f64) -> f32 {
+        x as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn pi() -> f32 {
+        std::f32::consts::PI
+    }
+
+    fn infinity() -> f32 {
+        f32::INFINITY
+    }
+
+    fn nan() -> f32 {
+        f32::NAN
+    }
+
+    fn epsilon() -> f32 {
+        f32::EPSILON
+    }
+
+    fn sqrt(self) -> f32 {
+        f32::sqrt(self)
+    }
+
+    fn cbrt(self) -> f32 {
+        f32::cbrt(self)
+    }
+
+    fn exp(self) -> f32 {
+        f32::exp(self)
+    }
+
+    fn ln(self) -> f32 {
+        f32::ln(self)
+    }
+
+    fn ln_1p(self) -> f32 {
+        f32::ln_1p(self)
+    }
+
+    fn sin(self) -> f32 {
+        f32::sin(self)
+    }
+
+    fn cos(self) -> f32 {
+        f32::cos(self)
+    }
+
+    fn sinh(self) -> f32 {
+        f32::sinh(self)
+    }
+
+    fn cosh(self) -> f32 {
+        f32::cosh(self)
+    }
+
+    fn asinh(self) -> f32 {
+        f32::asinh(self)
+    }
+
+    fn acos(self) -> f32 {
+        f32::acos(self)
+    }
+
+    fn floor(self) -> f32 {
+        f32::floor(self)
+    }
+
+    fn powf(self, x: f32) -> f32 {
+        f32::powf(self, x)
+    }
+
+    fn atan2(self, x: f32) -> f32 {
+        f32::atan2(self, x)
+    }
+
+    fn hypot(self, other: f32) -> f32 {
+        f32::hypot(self, other)
+    }
+
+    fn copysign(self, sign: f32) -> f32 {
+        f32::copysign(self, sign)
+    }
+
+    fn max(self, other: f32) -> f32 {
+        f32::max(self, other)
+    }
+
+    fn min(self, other: f32) -> f32 {
+        f32::min(self, other)
+    }
+}
+
+impl ComplexField for f32 {
+    fn from_real(x: f32) -> f32 {
+        x
+    }
+
+    fn re(self) -> f32 {
+        self
+    }
+
+    fn im(self) -> f32 {
+        0.0
+    }
+}
+//TRAIT IMPLEMENTATION END
+
+//borrowed forms of a binary operator, all forwarding to the owned one
+//the second form is for mixed operands, like a scalar on either side
+macro_rules! forward_ref_binop {
+    ([$($gen:tt)*] $imp:ident, $method:ident for $ty:ty) => {
+        forward_ref_binop!([$($gen)*] $imp, $method for $ty, $ty => $ty);
+    };
+
+    ([$($gen:tt)*] $imp:ident, $method:ident for $lhs:ty, $rhs:ty => $out:ty) => {
+        impl<'a, $($gen)*> $imp<&'a $rhs> for $lhs {
+            type Output = $out;
+            fn $method(self, other: &'a $rhs) -> $out {
+                $imp::$method(self, *other)
+            }
+        }
+
+        impl<'a, $($gen)*> $imp<$rhs> for &'a $lhs {
+            type Output = $out;
+            fn $method(self, other: $rhs) -> $out {
+                $imp::$method(*self, other)
+            }
+        }
+
+        impl<'a, 'b, $($gen)*> $imp<&'b $rhs> for &'a $lhs {
+            type Output = $out;
+            fn $method(self, other: &'b $rhs) -> $out {
+                $imp::$method(*self, *other)
+            }
+        }
+    };
+}
+
+//borrowed form of a unary operator
+macro_rules! forward_ref_unop {
+    ([$($gen:tt)*] $imp:ident, $method:ident for $ty:ty) => {
+        impl<'a, $($gen)*> $imp for &'a $ty {
+            type Output = $ty;
+            fn $method(self) -> $ty {
+                $imp::$method(*self)
+            }
+        }
+    };
+}
+
+//compound assignment with a borrowed right hand side
+macro_rules! forward_ref_op_assign {
+    ([$($gen:tt)*] $imp:ident, $method:ident for $ty:ty, $rhs:ty) => {
+        impl<'a, $($gen)*> $imp<&'a $rhs> for $ty {
+            fn $method(&mut self, other: &'a $rhs) {
+                $imp::$method(self, *other);
+            }
+        }
+    };
+}
@@ -0,0 +1,174 @@
+use ComplexNumbersModule::*;
+use std::f64::consts::PI;
+
+//exact on the components and on the sign of zeros
+fn assert_close(z: ComplexNumber, re: f64, im: f64) {
+    let close = |a: f64, b: f64| (a - b).abs() <= 1e-12 * b.abs().max(1.0) && a.is_sign_negative() == b.is_sign_negative();
+    assert!(close(z.Re, re) && close(z.Im, im), "expected {}{:+}i, got {}", re, im, z);
+}
+
+#[test]
+fn sqrt_branch_cut() {
+    assert_close(complex!(-4.0, 0.0).sqrt(), 0.0, 2.0);
+    assert_close(complex!(-4.0, -0.0).sqrt(), 0.0, -2.0);
+    assert_close(complex!(0.0, -0.0).sqrt(), 0.0, -0.0);
+    assert_close(complex!(4.0, 0.0).sqrt(), 2.0, 0.0);
+}
+
+#[test]
+fn ln_branch_cut() {
+    assert_close(complex!(-1.0, 0.0).ln(), 0.0, PI);
+    assert_close(complex!(-1.0, -0.0).ln(), 0.0, -PI);
+}
+
+#[test]
+fn asin_branch_cut() {
+    let im = (2.0 + 3f64.sqrt()).ln();
+    assert_close(complex!(2.0, 0.0).asin(), PI / 2.0, im);
+    assert_close(complex!(2.0, -0.0).asin(), PI / 2.0, -im);
+    assert_close(complex!(-2.0, 0.0).asin(), -PI / 2.0, im);
+    assert_close(complex!(-2.0, -0.0).asin(), -PI / 2.0, -im);
+}
+
+#[test]
+fn acosh_branch_cut() {
+    let re = (2.0 + 3f64.sqrt()).ln();
+    assert_close(complex!(-2.0, 0.0).acosh(), re, PI);
+    assert_close(complex!(-2.0, -0.0).acosh(), re, -PI);
+}
+
+#[test]
+fn typed_macro_casts() {
+    let z = complex!(1, 2; f64);
+    assert_eq!((z.Re, z.Im), (1.0, 2.0));
+    let w = complex!(1.5, -2; f32);
+    assert_eq!((w.Re, w.Im), (1.5f32, -2.0f32));
+    let q = quaternion!(1, 2, 3, 4; f64);
+    assert_eq!((q.Re, q.Km), (1.0, 4.0));
+}
+
+#[test]
+#[allow(clippy::op_ref)]
+fn borrowed_operands() {
+    let z = complex!(1, 2);
+    let w = complex!(3, -1);
+    assert_eq!(-&z, -z);
+    assert_eq!(&z + 1.0, z + 1.0);
+    assert_eq!(z * &2.0, z * 2.0);
+    assert_eq!(&z / &2.0, z / 2.0);
+    assert_eq!(2.0 * &z, 2.0 * z);
+    assert_eq!(&1.0 - &z, 1.0 - z);
+    assert_eq!(&1.0 / z, 1.0 / z);
+    let mut u = z;
+    u += &w;
+    u -= &1.0;
+    u *= &w;
+    u /= &w;
+    u *= &2.0;
+    assert_eq!(u, (z + w - 1.0) * w / w * 2.0);
+
+    let q = quaternion!(1, 2, 3, 4);
+    let p = quaternion!(0.5, -1, 0, 2);
+    assert_eq!(-&q, -q);
+    assert_eq!(2.0 * &q, 2.0 * q);
+    assert_eq!(&q + 1.0, q + 1.0);
+    assert_eq!(&q / &p, q / p);
+    let mut r = q;
+    r += &p;
+    r *= &p;
+    r /= &2.0;
+    r -= &p;
+    assert_eq!(r, (q + p) * p / 2.0 - p);
+    let s = Complex::<f32> { Re: 1.0, Im: 1.0 };
+    assert_eq!(&2.0f32 * &s, 2.0f32 * s);
+}
+
+#[test]
+fn checked_division_agrees_with_operator() {
+    let inf = f64::INFINITY;
+    let cases = [
+        (complex!(1, 2), complex!(3, -1)),
+        (complex!(1, 1), complex!(0, 0)),
+        (complex!(1, 0), complex!(0, 0)),
+        (complex!(0, 0), complex!(0, 0)),
+        (complex!(inf, 1), complex!(1, 2)),
+        (complex!(1, 2), complex!(inf, 0)),
+        (complex!(inf, 0), complex!(inf, 0)),
+        (complex!(f64::NAN, 1), complex!(1, 1)),
+        (complex!(1e300, 1e300), complex!(1e-300, 1e-300))
+    ];
+    for (a, b) in cases {
+        let quotient = a / b;
+        match Complex::div(&a, b) {
+            Some(q) => assert!(q == quotient, "{} / {}", a, b),
+            None => assert!(quotient.Re.is_nan() || quotient.Im.is_nan(), "{} / {}", a, b)
+        }
+    }
+    assert_eq!(Complex::div(&complex!(1, 1), complex!(0, 0)), Some(complex!(inf, inf)));
+    assert_eq!(Complex::div(&complex!(1, 2), complex!(inf, 0)), Some(complex!(0, 0)));
+    assert_eq!(Complex::div(&complex!(0, 0), complex!(0, 0)), None);
+    assert_eq!(Complex::div(&complex!(1, 0), complex!(0, 0)), None);
+}
+
+fn relative_error(z: ComplexNumber, re: f64, im: f64) -> f64 {
+    ((z.Re - re).abs() / re.abs().max(f64::MIN_POSITIVE)).max((z.Im - im).abs() / im.abs().max(f64::MIN_POSITIVE))
+}
+
+#[test]
+fn inverse_functions_near_zero() {
+    //all of these are z + O(z^3), so both parts must come back to full precision
+    for scale in [1e-5, 1e-10, 1e-17, 1e-200] {
+        let z = complex!(scale, scale);
+        for (name, w) in [("asin", z.asin()), ("asinh", z.asinh()), ("atan", z.atan()), ("atanh", z.atanh())] {
+            assert!(relative_error(w, scale, scale) < 1e-15 + scale * scale, "{}({}) = {}", name, z, w);
+        }
+    }
+}
+
+#[test]
+fn acos_near_one() {
+    for x in [1.0 - 1e-10, 1.0 - 1e-15, 0.999] {
+        assert!(relative_error(complex!(x, 0.0).acos(), x.acos(), 0.0) < 1e-14);
+    }
+    //Im acos(x + iy) = -y / sqrt(1 - x^2) for tiny y
+    let w = complex!(0.5, 1e-20).acos();
+    assert!(relative_error(w, 0.5f64.acos(), -1e-20 / 0.75f64.sqrt()) < 1e-14, "{}", w);
+}
+
+#[test]
+fn inverse_functions_invert() {
+    for (re, im) in [(0.3, 0.4), (-0.7, 1.9), (2.5, -0.1), (-3.0, -2.0), (0.0, 0.5), (0.9, 0.0)] {
+        let z = complex!(re, im);
+        assert!((z.asin().sin() - z).abs() < 1e-14, "asin {}", z);
+        assert!((z.acos().cos() - z).abs() < 1e-14, "acos {}", z);
+        assert!((z.asinh().sinh() - z).abs() < 1e-14, "asinh {}", z);
+        assert!((z.acosh().cosh() - z).abs() < 1e-14, "acosh {}", z);
+        assert!((z.atanh().tanh() - z).abs() < 1e-14, "atanh {}", z);
+        assert!((z.atan().tan() - z).abs() < 1e-14, "atan {}", z);
+    }
+    assert_close(complex!(2.0, 0.0).atanh(), 3f64.ln() / 2.0, PI / 2.0);
+    assert_close(complex!(2.0, -0.0).atanh(), 3f64.ln() / 2.0, -PI / 2.0);
+}
+
+#[test]
+fn sqrt_near_overflow() {
+    let w = complex!(1e308, 1e308).sqrt();
+    let expect = complex!(1e308, 1e308).polar();
+    let (r, theta) = (expect.r.sqrt(), expect.theta / 2.0);
+    assert!(relative_error(w, r * theta.cos(), r * theta.sin()) < 1e-15, "{}", w);
+    let w = complex!(-f64::MAX, 1.0).sqrt();
+    assert!(w.Re.is_finite() && relative_error(w, w.Re, f64::MAX.sqrt()) < 1e-15, "{}", w);
+}
+
+#[test]
+fn magnitudes_keep_the_component_type() {
+    //norms come back in the real type of the components, f32 stays f32
+    let z: Complex<f32> = Complex { Re: 3.0, Im: 4.0 };
+    let (abs, norm): (f32, f32) = (DivisionRing::abs(z), DivisionRing::norm_sqr(z));
+    assert!(abs == 5.0 && norm == 25.0);
+    let w = z.map_parts(|x: f32| x * 0.5);
+    assert!(w.Re == 1.5 && w.Im == 2.0);
+    let q: Quaternion<f32> = Quaternion { Re: 1.0, Im: 1.0, Jm: 1.0, Km: 1.0 };
+    let abs: f32 = DivisionRing::abs(q);
+    assert!(abs == 2.0 && DivisionRing::norm_sqr(-2.0f32) == 4.0f32);
+}
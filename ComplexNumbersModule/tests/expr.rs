@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use ComplexNumbersModule::*;
+use ComplexNumbersModule::expr::*;
+
+fn eval(s: &str) -> std::result::Result<Value, ExprError> {
+    evaluate(s, &HashMap::new(), &Functions::standard())
+}
+
+fn text(s: &str) -> String {
+    eval(s).unwrap().to_string()
+}
+
+fn check(s: &str) -> std::result::Result<Type, ExprError> {
+    parse(s)?.check(&HashMap::new(), &Functions::standard())
+}
+
+#[test]
+fn precedence() {
+    //unary minus binds looser than ^, which is right associative
+    assert_eq!(text("-2^2"), "-4");
+    assert_eq!(text("(-2)^2"), "4");
+    assert_eq!(text("2^3^2"), "512");
+    assert_eq!(text("(2^3)^2"), "64");
+    assert_eq!(text("2^-1"), "0.5");
+    assert_eq!(text("-2^-2"), "-0.25");
+    assert_eq!(text("2*3^2"), "18");
+    //the other operators are left associative
+    assert_eq!(text("1-2-3"), "-4");
+    assert_eq!(text("8/4/2"), "1");
+    assert_eq!(text("1+2*3-4/2"), "5");
+    assert_eq!(text("--3"), "3");
+}
+
+#[test]
+fn static_type_errors() {
+    assert!(matches!(check("trans([[1, 2]])"), Ok(Type::Matrix)) && matches!(check("1 + j"), Ok(Type::Quaternion)) && matches!(check("re(j)"), Ok(Type::Complex)));
+    assert!(matches!(check("[[1]] + 1"),
+        Err(ExprError::TypeMismatch { position: 6, op: '+', left: Type::Matrix, right: Type::Complex })));
+    assert!(matches!(check("1 + j * [[1]]"),
+        Err(ExprError::TypeMismatch { position: 6, op: '*', left: Type::Quaternion, right: Type::Matrix })));
+    assert!(matches!(check("2 * sin(j)"), Err(ExprError::ArgumentType { position: 4, .. })));
+    assert!(matches!(check("det(1, 2)"), Err(ExprError::ArgumentCount { position: 0, expected: 1, found: 2, .. })));
+    assert!(matches!(check("1 + foo(1)"), Err(ExprError::UnknownFunction { position: 4, .. })));
+    assert!(matches!(check("x + 1"), Err(ExprError::UnknownVariable { position: 0, .. })));
+    assert!(matches!(check("[[1, [[2]]]]"), Err(ExprError::MatrixEntry { position: 5, found: Type::Matrix })));
+    //checking never evaluates, so 1/0 type checks fine
+    assert!(matches!(check("1/0"), Ok(Type::Complex)));
+    assert_eq!(check("sin(j)").unwrap_err().to_string(), "sin() is not defined for (quaternion) at 0");
+}
+
+#[test]
+fn implicit_units() {
+    assert_eq!(text("3i"), "3i");
+    assert_eq!(text("2j"), "2j");
+    assert_eq!(text("-4k"), "-4k");
+    assert_eq!(text("1.5e2i"), "150i");
+    assert_eq!(text("1+2i+3j+4k"), "1+2i+3j+4k");
+    assert!(matches!(eval("3i").unwrap(), Value::Complex(_)) && matches!(eval("2j").unwrap(), Value::Quaternion(_)));
+    assert_eq!(text("2j*2j"), "-4");
+    assert_eq!(text("i*j"), "k");
+    assert_eq!(text("2*i"), "2i");
+    //only a unit letter ends the number, a longer name does not
+    assert!(matches!(eval("3in"), Err(ExprError::UnexpectedToken { position: 1, .. })));
+    assert!(matches!(eval("2 i"), Err(ExprError::UnexpectedToken { position: 2, .. })));
+}
+
+#[test]
+fn matrix_literals() {
+    assert_eq!(text("[[1, 2], [3, 4]]"), "[1.0, 2.0]\n[3.0, 4.0]");
+    assert_eq!(text("[1, 2, 3]"), "[1.0, 2.0, 3.0]");
+    assert_eq!(text("[[1+1, 2^2], [-1, re(3+4i)]]"), "[2.0, 4.0]\n[-1.0, 3.0]");
+    assert_eq!(text("det([[1, 2], [3, 4]])"), "-2");
+    assert_eq!(text("[[2, 0], [0, 4]]^-1 * [[1, 2], [3, 4]]"), "[0.5, 1.0]\n[0.75, 1.0]");
+    assert!(matches!(eval("[[1, 2], [3]]"),
+        Err(ExprError::Matrix { position: 0, error: MatrixError::RaggedRows { row: 1, expected: 2, found: 1 } })));
+    assert!(matches!(eval("[[1, i]]"), Err(ExprError::MatrixEntry { position: 5, found: Type::Complex })));
+    //real quaternions are real entries, for check and eval alike
+    assert!(matches!(check("[[j*j, 1]]"), Ok(Type::Matrix)));
+    assert_eq!(text("[[j*j, 1]]"), "[-1.0, 1.0]");
+    assert!(matches!(check("[[j]]"), Ok(Type::Matrix)));
+    assert!(matches!(eval("[[2, j]]"), Err(ExprError::MatrixEntry { position: 5, found: Type::Quaternion })));
+}
+
+#[test]
+fn division_by_zero() {
+    for (s, position) in [("1/0", 1), ("(2+i) / (i - i)", 6), ("j/0", 1), ("2 * [[1]]/0", 9), ("0/0", 1), ("i/(j-j)", 1)] {
+        match eval(s) {
+            Err(ExprError::Domain { position: at, message }) => assert!(at == position && message == "division by zero", "{}", s),
+            other => panic!("{} gave {:?}", s, other)
+        }
+    }
+    assert!(matches!(eval("inverse(0)"), Err(ExprError::Domain { position: 0, message }) if message == "zero has no inverse"));
+    assert!(matches!(eval("inv(j - j)"), Err(ExprError::Domain { position: 0, .. })));
+    assert_eq!(text("inverse(2i)"), "-0.5i");
+    assert_eq!(text("1/(2i)"), "-0.5i");
+    //a tiny divisor is not zero
+    assert!(eval("1/1e-300").unwrap().real().is_some_and(|x| (x / 1e300 - 1.0).abs() < 1e-15));
+}
+
+#[test]
+fn custom_functions() {
+    let mut functions = Functions::new();
+    functions.insert_unary("double", |t| {(t != Type::Matrix).then_some(t)}, |x| match x {
+        Value::Complex(z) => Ok(Value::Complex(*z + *z)),
+        Value::Quaternion(q) => Ok(Value::Quaternion(*q + *q)),
+        _ => unreachable!()
+    });
+    functions.insert_unary("fail", |_| Some(Type::Complex), |_| Err("always fails".to_string()));
+    let vars = HashMap::new();
+    let run = |s: &str| evaluate(s, &vars, &functions);
+    assert_eq!(run("double(3i) + 1").unwrap().to_string(), "1+6i");
+    assert_eq!(run("double(double(j))").unwrap().to_string(), "4j");
+    assert!(matches!(run("double([[1]])"), Err(ExprError::ArgumentType { position: 0, .. })));
+    assert!(matches!(run("1 + fail(2)"), Err(ExprError::Domain { position: 4, message }) if message == "always fails"));
+    //only the functions in the table exist
+    assert!(matches!(run("sin(1)"), Err(ExprError::UnknownFunction { position: 0, .. })));
+    assert_eq!(functions.names(), ["double", "fail"]);
+    let mut session = Session::new();
+    session.functions = functions;
+    assert_eq!(session.run("x = double(2); sin(x)"), ["x = 4", "error: unknown function sin() at 15"]);
+    assert_eq!(session.run("double = 1"), ["error: cannot assign to the function double"]);
+    assert!(session.functions.remove("double").is_some() && !session.functions.contains("double"));
+    assert_eq!(session.run("double = 1"), ["double = 1"]);
+}
+
+#[test]
+fn session_assignment_and_ans() {
+    let mut session = Session::new();
+    assert_eq!(session.run("x = 2"), ["x = 2"]);
+    assert_eq!(session.run("ans * 3"), ["6"]);
+    assert_eq!(session.run("ans + x"), ["8"]);
+    //assigning also sets ans, and reassigning replaces the value
+    assert_eq!(session.run("x = x + 1; ans"), ["x = 3", "3"]);
+    assert_eq!(session.run("q = 1+2i+3j+4k; q * conj(q)"), ["q = 1+2i+3j+4k", "30"]);
+    assert_eq!(session.run("m = [[1, 2], [3, 4]]; det(m)"), ["m =\n[1.0, 2.0]\n[3.0, 4.0]", "-2"]);
+    assert_eq!(session.variables(), ["ans = -2", "m =\n[1.0, 2.0]\n[3.0, 4.0]", "q = 1+2i+3j+4k", "x = 3"]);
+    assert!(session.run("  x  =  5 ") == ["x = 5"] && session.vars.len() == 4);
+}
+
+#[test]
+fn session_statements() {
+    let mut session = Session::new();
+    //empty statements are skipped, every other one prints a line
+    assert_eq!(session.run("a = 1; ; b = a + 1;; a * b;"), ["a = 1", "b = 2", "2"]);
+    assert!(session.run("").is_empty() && session.run(" ; ").is_empty());
+    //the first error ends the line, earlier statements have already taken effect
+    assert_eq!(session.run("c = 5; c +* 1; d = 7"), ["c = 5", "error: unexpected '*' at 10"]);
+    assert!(session.vars.contains_key("c") && !session.vars.contains_key("d"));
+    //== is not an assignment
+    assert_eq!(session.run("a == 1"), ["error: unexpected '=' at 2"]);
+}
+
+#[test]
+fn session_error_positions() {
+    let mut session = Session::new();
+    //positions count from the start of the whole line, across assignments and statements
+    assert_eq!(session.run("1 + )"), ["error: unexpected ')' at 4"]);
+    assert_eq!(session.run("z = 1 + )"), ["error: unexpected ')' at 8"]);
+    assert_eq!(session.run("1; 2; y = (3"), ["1", "2", "error: unexpected end of input at 12"]);
+    assert_eq!(session.run("x = 1; y = foo + 1"), ["x = 1", "error: unknown variable foo at 11"]);
+    assert_eq!(session.run("é = 1; 1 + )"), ["é = 1", "error: unexpected ')' at 11"]);
+}
+
+#[test]
+fn session_function_names() {
+    let mut session = Session::new();
+    assert_eq!(session.run("norm = 3"), ["error: cannot assign to the function norm"]);
+    assert_eq!(session.run("x = 1; det = 2; y = 3"), ["x = 1", "error: cannot assign to the function det"]);
+    assert!(!session.vars.contains_key("norm") && !session.vars.contains_key("y"));
+    assert_eq!(session.run("norm(3+4i)"), ["5"]);
+    //a name that only starts like a function is a plain variable
+    assert_eq!(session.run("norms = 3"), ["norms = 3"]);
+    assert!(session.help().iter().any(|line| line.starts_with("functions: ") && line.contains("norm")));
+}
@@ -0,0 +1,85 @@
+use ComplexNumbersModule::*;
+use ComplexNumbersModule::fft::*;
+use std::f64::consts::PI;
+
+//the O(n^2) definition, X_k = sum of x_j exp(-2 pi i jk / n)
+fn dft(x: &[ComplexNumber]) -> Vec<ComplexNumber> {
+    let n = x.len();
+    (0..n).map(|k| (0..n).map(|j| {
+        let theta = -2.0 * PI * ((j * k) % n) as f64 / n as f64;
+        x[j] * complex!(theta.cos(), theta.sin())
+    }).sum()).collect()
+}
+
+fn max_error(a: &[ComplexNumber], b: &[ComplexNumber]) -> f64 {
+    assert_eq!(a.len(), b.len());
+    a.iter().zip(b).map(|(x, y)| (*x - *y).abs()).fold(0.0, f64::max)
+}
+
+fn signal(n: usize) -> Vec<ComplexNumber> {
+    (0..n).map(|j| complex!((j as f64 * 0.37).sin(), (j as f64 * 1.3).cos() - 0.2)).collect()
+}
+
+fn real_signal(n: usize) -> Vec<f64> {
+    (0..n).map(|j| (j as f64 * 0.7).cos() + j as f64 * 0.01).collect()
+}
+
+//forward against the naive transform, then back again
+fn check_complex(n: usize) {
+    let x = signal(n);
+    let mut y = x.clone();
+    fft(&mut y);
+    assert!(max_error(&y, &dft(&x)) < 1e-10 * (n as f64 + 1.0), "fft of length {}", n);
+    ifft(&mut y);
+    assert!(max_error(&y, &x) < 1e-12 * (n as f64 + 1.0), "ifft of length {}", n);
+}
+
+#[test]
+fn power_of_two_lengths() {
+    for n in [0, 1, 2, 4, 8, 16, 64, 256, 1024] {
+        check_complex(n);
+    }
+}
+
+#[test]
+fn prime_lengths() {
+    //these go through bluestein's convolution
+    for n in [3, 5, 7, 17, 31, 127, 257, 1009] {
+        check_complex(n);
+    }
+}
+
+#[test]
+fn composite_lengths() {
+    for n in [6, 12, 100, 1000] {
+        check_complex(n);
+    }
+}
+
+#[test]
+fn real_input() {
+    //even lengths are packed into a half length transform, odd ones are not
+    for n in [1, 2, 3, 4, 5, 6, 7, 8, 15, 16, 17, 100, 101, 1000] {
+        let x = real_signal(n);
+        let full = dft(&x.iter().map(|&v| complex!(v, 0)).collect::<Vec<_>>());
+        let half = rfft(&x);
+        assert_eq!(half.len(), n / 2 + 1);
+        assert!(max_error(&half, &full[..n / 2 + 1]) < 1e-10 * n as f64, "rfft of length {}", n);
+    }
+}
+
+#[test]
+fn planner_reuses_plans() {
+    let mut planner = FftPlanner::new();
+    assert!(std::sync::Arc::ptr_eq(&planner.plan(12), &planner.plan(12)));
+    assert!(std::sync::Arc::ptr_eq(&planner.plan_real(12), &planner.plan_real(12)));
+    for n in [12, 13, 64] {
+        let x = signal(n);
+        let (mut a, mut b) = (x.clone(), x.clone());
+        fft(&mut a);
+        planner.plan(n).process(&mut b);
+        assert!(max_error(&a, &b) < 1e-12 * n as f64);
+        let r = real_signal(n);
+        assert!(max_error(&planner.plan_real(n).process(&r), &rfft(&r)) < 1e-12 * n as f64);
+    }
+}
@@ -0,0 +1,296 @@
+use ComplexNumbersModule::*;
+
+#[test]
+fn non_square_lu_is_an_error() {
+    let m = matrix!(2, 3, vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+    assert!(m.inverse().is_none());
+    assert!(matches!(m.det(), Err(MatrixError::NotSquare { shape: (2, 3) })));
+    assert!(matches!(m.lu(), Err(MatrixError::NotSquare { shape: (2, 3) })));
+    assert!((matrix!(2, 2, vec![vec![1.0, 2.0], vec![3.0, 4.0]]).det().unwrap() + 2.0).abs() < 1e-12);
+}
+
+//entries in [-1, 1) from a fixed linear congruential sequence
+fn random_matrix(n: usize, seed: u64) -> Matrix {
+    let mut state = seed;
+    let data = (0..n * n).map(|_| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+    }).collect();
+    Matrix::from_vec(n, n, data).unwrap()
+}
+
+//largest |Av - lambda v| over the eigenpairs, relative to the size of A
+fn residual(a: &Matrix, values: &[ComplexNumber], vectors: &Matrice<ComplexNumber>) -> f64 {
+    let n = a.height;
+    let scale = a.as_slice().iter().fold(1.0, |m: f64, x| m.max(x.abs()));
+    (0..n).map(|k| {
+        (0..n).map(|i| {
+            let av: ComplexNumber = (0..n).map(|j| vectors[(j, k)] * a[(i, j)]).sum();
+            (av - values[k] * vectors[(i, k)]).abs()
+        }).fold(0.0, f64::max)
+    }).fold(0.0, f64::max) / scale
+}
+
+#[test]
+fn eigen_residuals() {
+    for (n, seed) in [(3, 1), (6, 2), (10, 3), (17, 4)] {
+        let a = random_matrix(n, seed);
+        let eigen = a.eigen().unwrap();
+        assert_eq!(eigen.values.len(), n);
+        assert!(residual(&a, &eigen.values, &eigen.vectors) < 1e-12, "{}x{}", n, n);
+        //eigenvectors are unit length
+        for k in 0..n {
+            let len: f64 = (0..n).map(|i| eigen.vectors[(i, k)].norm_sqr()).sum();
+            assert!((len - 1.0).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn eigen_complex_pairs() {
+    //a rotation by a quarter turn has eigenvalues +-i
+    let a = matrix!(2, 2, vec![vec![0.0, -1.0], vec![1.0, 0.0]]);
+    let eigen = a.eigen().unwrap();
+    assert!(residual(&a, &eigen.values, &eigen.vectors) < 1e-14);
+    for target in [complex!(0, 1), complex!(0, -1)] {
+        assert!(eigen.values.iter().any(|&z| (z - target).abs() < 1e-14));
+    }
+    //random real matrices, complex eigenvalues come in conjugate pairs
+    let mut pairs = 0;
+    for seed in 5..10 {
+        let a = random_matrix(8, seed);
+        let eigen = a.eigen().unwrap();
+        assert!(residual(&a, &eigen.values, &eigen.vectors) < 1e-12);
+        for z in eigen.values.iter().filter(|z| z.Im.abs() > 1e-10) {
+            pairs += 1;
+            assert!(eigen.values.iter().any(|w| (*w - z.conjugate()).abs() < 1e-10));
+        }
+    }
+    assert!(pairs > 0);
+}
+
+#[test]
+fn eigen_symmetric() {
+    let r = random_matrix(8, 11);
+    let a = r.clone() + r.trans();
+    let eigen = a.symmetric_eigen().unwrap();
+    assert!(eigen.values.windows(2).all(|w| w[0] <= w[1]));
+    let values: Vec<ComplexNumber> = eigen.values.iter().map(|&x| complex!(x, 0)).collect();
+    let vectors = Matrice::from_vec(8, 8, eigen.vectors.as_slice().iter().map(|&x| complex!(x, 0)).collect()).unwrap();
+    assert!(residual(&a, &values, &vectors) < 1e-12);
+    //the eigenbasis is orthonormal
+    let gram = eigen.vectors.trans() * eigen.vectors.clone();
+    let identity = Matrix::identitymat(8);
+    assert!(gram.as_slice().iter().zip(identity.as_slice()).all(|(x, y)| (x - y).abs() < 1e-12));
+    //the general solver routes symmetric input through the same method
+    let general = a.eigen().unwrap();
+    assert!(general.values.iter().all(|z| z.Im == 0.0));
+    assert!(residual(&a, &general.values, &general.vectors) < 1e-12);
+    assert!(matches!(random_matrix(3, 12).symmetric_eigen(), Err(MatrixError::NotSymmetric)));
+}
+
+#[test]
+fn integer_matrix_arithmetic() {
+    let a = Matrice::<i32>::new(2, 2, vec![vec![1, 2], vec![3, 4]]);
+    let b = Matrice::<i32>::identitymat(2);
+    assert_eq!((a.clone() + b.clone()).as_slice(), &[2, 2, 3, 5]);
+    assert_eq!((a.clone() - b.clone()).as_slice(), &[0, 2, 3, 3]);
+    assert_eq!((a.clone() * a.clone()).as_slice(), &[7, 10, 15, 22]);
+    assert_eq!((a.clone() * 3).as_slice(), &[3, 6, 9, 12]);
+    assert!(a.checked_mul(&Matrice::<i32>::new_zero(3, 1)).is_err());
+}
+
+fn random_complex_matrix(height: usize, width: usize, seed: u64) -> Matrice<ComplexNumber> {
+    let re = random_matrix(height.max(width), seed);
+    let im = random_matrix(height.max(width), seed + 100);
+    let data = (0..height).flat_map(|x| (0..width).map(move |y| (x, y))).map(|(x, y)| complex!(re[(x, y)], im[(x, y)])).collect();
+    Matrice::from_vec(height, width, data).unwrap()
+}
+
+fn max_difference(a: &Matrice<ComplexNumber>, b: &Matrice<ComplexNumber>) -> f64 {
+    assert_eq!(a.shape(), b.shape());
+    a.as_slice().iter().zip(b.as_slice()).map(|(x, y)| (*x - *y).abs()).fold(0.0, f64::max)
+}
+
+#[test]
+fn complex_qr() {
+    let a = random_complex_matrix(6, 4, 21);
+    let qr = a.qr();
+    let q = qr.q();
+    assert!(max_difference(&(q.clone() * qr.r.clone()), &a) < 1e-12);
+    assert!(max_difference(&(q.adjoint() * q), &Matrice::identitymat(6)) < 1e-12);
+    assert!((0..6).all(|x| (0..x.min(4)).all(|y| qr.r[(x, y)].is_zero())));
+    //the least squares residual is orthogonal to the columns of A
+    let b: Vec<ComplexNumber> = (0..6).map(|k| complex!(k as f64, 1.0 - k as f64)).collect();
+    let x = a.lstsq(&b).unwrap();
+    let residual: Vec<ComplexNumber> = (0..6).map(|i| (0..4).map(|j| a[(i, j)] * x[j]).sum::<ComplexNumber>() - b[i]).collect();
+    for j in 0..4 {
+        let projection: ComplexNumber = (0..6).map(|i| a[(i, j)].conjugate() * residual[i]).sum();
+        assert!(projection.abs() < 1e-12);
+    }
+}
+
+#[test]
+fn complex_cholesky() {
+    let b = random_complex_matrix(5, 5, 31);
+    let a = b.adjoint() * b + Matrice::identitymat(5);
+    let l = a.cholesky().unwrap();
+    assert!(max_difference(&(l.clone() * l.adjoint()), &a) < 1e-12);
+    assert!((0..5).all(|k| l[(k, k)].Im == 0.0 && l[(k, k)].Re > 0.0));
+    assert!(matches!((Matrice::<ComplexNumber>::identitymat(2) * complex!(-1, 0)).cholesky(), Err(MatrixError::NotPositiveDefinite)));
+}
+
+#[test]
+fn complex_svd() {
+    for (m, n) in [(5, 3), (3, 5), (4, 4)] {
+        let a = random_complex_matrix(m, n, 41 + m as u64);
+        let svd = a.svd();
+        let k = m.min(n);
+        let mut s = Matrice::<ComplexNumber>::new_zero(k, k);
+        for (i, &x) in svd.s.iter().enumerate() {
+            s[(i, i)] = complex!(x, 0);
+        }
+        assert!(max_difference(&(svd.u.clone() * s * svd.v.adjoint()), &a) < 1e-12);
+        assert!(max_difference(&(svd.u.adjoint() * svd.u.clone()), &Matrice::identitymat(k)) < 1e-12);
+        assert!(max_difference(&(svd.v.adjoint() * svd.v.clone()), &Matrice::identitymat(k)) < 1e-12);
+        assert!(svd.s.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(a.rank(), k);
+        //A A^+ A = A
+        let pinv = a.pinv();
+        assert!(max_difference(&(a.clone() * pinv * a.clone()), &a) < 1e-12);
+    }
+}
+
+#[test]
+fn real_factorizations() {
+    let a = random_matrix(5, 51);
+    let qr = a.qr();
+    let product = qr.q() * qr.r.clone();
+    assert!(product.as_slice().iter().zip(a.as_slice()).all(|(x, y)| (x - y).abs() < 1e-12));
+    let spd = a.trans() * a.clone() + Matrix::identitymat(5);
+    let l = spd.cholesky().unwrap();
+    let product = l.clone() * l.trans();
+    assert!(product.as_slice().iter().zip(spd.as_slice()).all(|(x, y)| (x - y).abs() < 1e-12));
+    let singular = matrix!(3, 3, vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.0], vec![1.0, 0.0, 1.0]]);
+    assert_eq!(singular.rank(), 2);
+    assert!(singular.cond().is_infinite());
+    assert!(matrix!(2, 2, vec![vec![1.0, 2.0], vec![2.0, 1.0]]).is_symmetric());
+}
+
+#[test]
+fn matrix_power_by_squaring() {
+    let fibonacci = Matrice::<i64>::new(2, 2, vec![vec![1, 1], vec![1, 0]]);
+    assert!(fibonacci.pow(0).unwrap() == Matrice::identitymat(2));
+    assert!(fibonacci.pow(1).unwrap() == fibonacci);
+    assert_eq!(fibonacci.pow(90).unwrap()[(0, 1)], 2880067194370816120);
+    //a huge exponent takes about 31 squarings, not two billion products
+    let a = matrix!(2, 2, vec![vec![0.5, 0.25], vec![0.25, 0.5]]);
+    assert!(a.pow(2_000_000_000).unwrap().as_slice().iter().all(|x| *x == 0.0));
+    assert!(matches!(matrix!(2, 3).pow(2), Err(MatrixError::NotSquare { shape: (2, 3) })));
+}
+
+#[test]
+fn solve_residuals() {
+    for (n, seed) in [(3, 61), (7, 62), (12, 63)] {
+        let a = random_matrix(n, seed);
+        let b: Vec<f64> = (0..n).map(|k| k as f64 - 2.0).collect();
+        let x = a.solve(&b).unwrap();
+        for i in 0..n {
+            let ax: f64 = (0..n).map(|j| a[(i, j)] * x[j]).sum();
+            assert!((ax - b[i]).abs() < 1e-12, "{}x{} row {}", n, n, i);
+        }
+        //several right hand sides at once, column k of X solves against column k of B
+        let rhs = random_matrix(n, seed + 10);
+        let x = a.solve_matrix(&rhs).unwrap();
+        let residual = a.clone() * x - rhs;
+        assert!(residual.as_slice().iter().all(|r| r.abs() < 1e-12));
+    }
+    //complex and quaternion systems go through the same elimination
+    let a = random_complex_matrix(4, 4, 64);
+    let b: Vec<ComplexNumber> = (0..4).map(|k| complex!(1, k)).collect();
+    let x = a.solve(&b).unwrap();
+    for i in 0..4 {
+        assert!(((0..4).map(|j| a[(i, j)] * x[j]).sum::<ComplexNumber>() - b[i]).abs() < 1e-12);
+    }
+    let a = Matrice::new(2, 2, vec![vec![quaternion!(1, 2, 0, 1), quaternion!(0, 0, 1, 0)], vec![quaternion!(3, 0, 0, 0), quaternion!(1, 1, 1, 1)]]);
+    let b = vec![quaternion!(1, 0, 0, 0), quaternion!(0, 1, 0, 0)];
+    let x = a.solve(&b).unwrap();
+    for i in 0..2 {
+        assert!(((0..2).map(|j| a[(i, j)] * x[j]).sum::<Quaternion>() - b[i]).norm() < 1e-12);
+    }
+}
+
+#[test]
+fn solve_errors() {
+    let singular = matrix!(3, 3, vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]]);
+    assert!(matches!(singular.solve(&[1.0, 2.0, 3.0]), Err(MatrixError::Singular)));
+    assert!(matches!(singular.solve_matrix(&Matrix::identitymat(3)), Err(MatrixError::Singular)));
+    assert!(singular.inverse().is_none());
+    let zero = Matrix::new_zero(2, 2);
+    assert!(matches!(zero.solve(&[1.0, 1.0]), Err(MatrixError::Singular)));
+    let a = Matrix::identitymat(3);
+    assert!(matches!(a.solve(&[1.0, 2.0]), Err(MatrixError::DimensionMismatch { left: (3, 3), right: (2, 1) })));
+    assert!(matches!(a.solve_matrix(&Matrix::identitymat(2)), Err(MatrixError::DimensionMismatch { .. })));
+    assert!(matches!(matrix!(2, 3).solve(&[1.0, 2.0]), Err(MatrixError::NotSquare { shape: (2, 3) })));
+}
+
+#[test]
+fn construction_errors() {
+    let m = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    assert!(m.shape() == (2, 3) && m[(1, 0)] == 4.0);
+    assert!(matches!(Matrix::from_vec(2, 3, vec![1.0; 5]), Err(MatrixError::DataLength { expected: 6, found: 5 })));
+    assert!(matches!(Matrix::from_vec(2, 2, vec![1.0; 6]), Err(MatrixError::DataLength { expected: 4, found: 6 })));
+    let rows = vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0]];
+    assert!(matches!(Matrix::from_rows(&rows), Err(MatrixError::RaggedRows { row: 2, expected: 2, found: 1 })));
+    let rows = vec![vec![1.0], vec![2.0, 3.0]];
+    assert!(matches!(Matrix::from_rows(&rows), Err(MatrixError::RaggedRows { row: 1, expected: 1, found: 2 })));
+    let m = Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+    assert!(m == matrix!(2, 2, vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+    //missing entries are zero filled by new
+    let m = matrix!(2, 2, vec![vec![1.0]]);
+    assert!(m.as_slice() == [1.0, 0.0, 0.0, 0.0]);
+}
+
+#[test]
+#[should_panic(expected = "data does not fit in a 2x2 matrix")]
+fn new_rejects_too_many_rows() {
+    matrix!(2, 2, vec![vec![1.0], vec![2.0], vec![3.0]]);
+}
+
+#[test]
+#[should_panic(expected = "2x3 data does not fit in a 2x2 matrix")]
+fn new_rejects_too_wide_rows() {
+    matrix!(2, 2, vec![vec![1.0], vec![2.0, 3.0, 4.0]]);
+}
+
+#[test]
+fn single_precision_tolerances() {
+    //rounding in f32 is far above f64::EPSILON, the tolerances have to follow the entry type
+    let a: Matrice<f32> = Matrice::new(3, 3, vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]]);
+    assert_eq!(a.rank(), 2);
+    assert!(a.cond().is_infinite());
+    let b: Matrice<f32> = Matrice::new(3, 3, vec![vec![0.1, 0.2, 0.3], vec![0.4, 0.5, 0.6], vec![0.7, 0.8, 0.9]]);
+    assert!(b.inverse().is_none());
+    assert!(matches!(b.solve(&[1.0, 2.0, 3.0]), Err(MatrixError::Singular)));
+    assert!(!b.is_symmetric() && (b.clone() * b.trans()).is_symmetric());
+    let c: Matrice<f32> = Matrice::new(2, 2, vec![vec![2.0, 1.0], vec![1.0, 3.0]]);
+    let inverse = c.inverse().unwrap();
+    assert!((c.clone() * inverse).as_slice().iter().zip([1.0, 0.0, 0.0, 1.0]).all(|(x, y)| (x - y).abs() < 1e-6));
+    assert_eq!(c.rank(), 2);
+}
+
+#[test]
+fn single_precision_complex_rank() {
+    //third row is the first plus i times the second
+    let z = |re: f32, im: f32| Complex { Re: re, Im: im };
+    let rows = [
+        vec![z(1.0, 0.5), z(0.3, -1.0), z(2.0, 0.0)],
+        vec![z(-0.7, 0.2), z(1.1, 0.4), z(0.0, 1.0)]
+    ];
+    let third: Vec<Complex<f32>> = rows[0].iter().zip(&rows[1]).map(|(&a, &b)| a + b * z(0.0, 1.0)).collect();
+    let a = Matrice::from_rows(&[rows[0].clone(), rows[1].clone(), third]).unwrap();
+    assert!(a.rank() < 3);
+    assert!(a.cond().is_infinite());
+    assert!(a.inverse().is_none());
+    assert!(!a.qr().is_full_rank());
+}
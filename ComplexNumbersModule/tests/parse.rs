@@ -0,0 +1,84 @@
+use ComplexNumbersModule::*;
+
+fn complex(s: &str) -> ComplexNumber {
+    s.parse().unwrap()
+}
+
+fn quaternion(s: &str) -> Quaternion {
+    s.parse().unwrap()
+}
+
+#[test]
+fn display_round_trip() {
+    let numbers = [
+        complex!(0, 0), complex!(-0.0, 0), complex!(0, 1), complex!(0, -1), complex!(1, 1), complex!(-2, -1),
+        complex!(12, 13), complex!(-0.5, 2.25), complex!(1e-20, -3.5), complex!(1e300, -1e-300),
+        complex!(0.1, 0.7), complex!(f64::MAX, f64::MIN_POSITIVE), complex!(3, f64::INFINITY)
+    ];
+    for z in numbers {
+        let text = z.to_string();
+        let back = complex(&text);
+        assert!(back == z && back.Re.is_sign_negative() == z.Re.is_sign_negative(), "{} came back as {}", text, back);
+    }
+    let quaternions = [
+        quaternion!(0, 0, 0, 0), quaternion!(1, 0, 0, 0), quaternion!(0, 1, -1, 1), quaternion!(1.5, 0, -2, 0),
+        quaternion!(-1, -1, -1, -1), quaternion!(0.1, 1e-7, -3e20, 0.3), quaternion!(0, 0, 0, -1)
+    ];
+    for q in quaternions {
+        let text = q.to_string();
+        assert!(quaternion(&text) == q, "{} came back as {}", text, quaternion(&text));
+    }
+    //a leading positive part has no sign
+    assert_eq!(quaternion!(0, 2, 0, 0).to_string(), "2i");
+    assert_eq!(quaternion!(0, 0, 2, -1).to_string(), "2j-k");
+    assert_eq!(quaternion!(0, 0, 0, 3.5).to_string(), "3.5k");
+    assert_eq!(quaternion!(0, 0, -2, 0).to_string(), "-2j");
+    let z: Complex<f32> = complex!(0.1, -0.2; f32);
+    assert!(z.to_string().parse::<Complex<f32>>().unwrap() == z);
+}
+
+#[test]
+fn accepted_forms() {
+    //a missing coefficient is one, with the sign in front of the unit
+    assert!(complex("i") == complex!(0, 1) && complex("-i") == complex!(0, -1) && complex("+i") == complex!(0, 1));
+    assert!(complex("j") == complex!(0, 1) && complex("-j") == complex!(0, -1) && complex("2-j") == complex!(2, -1));
+    assert!(quaternion("-j") == quaternion!(0, 0, -1, 0) && quaternion("i-j+k") == quaternion!(0, 1, -1, 1));
+    //exponents, with or without a sign and in either case
+    assert!(complex("1.5e3-2.5E-2i") == complex!(1500, -0.025));
+    assert!(complex("1e+2j") == complex!(0, 100) && complex("-.5e1") == complex!(-5, 0));
+    assert!(quaternion("2e-3+1E2k") == quaternion!(0.002, 0, 0, 100));
+    //terms may come in any order and whitespace is ignored around them
+    assert!(complex("  3i - 4 ") == complex!(-4, 3) && complex("1 + 2 i") == complex!(1, 2));
+    assert!(quaternion("k + 2 - 3j") == quaternion!(2, 0, -3, 1));
+    let special = complex("inf-nani");
+    assert!(special.Re == f64::INFINITY && special.Im.is_nan());
+    assert!(complex("-infinityi") == complex!(0, f64::NEG_INFINITY));
+}
+
+#[test]
+fn error_positions() {
+    let error = |s: &str| s.parse::<ComplexNumber>().err().unwrap();
+    assert!(matches!(error(""), ParseNumberError::Empty));
+    assert!(matches!(error("   "), ParseNumberError::Empty));
+    assert!(matches!(error("1+"), ParseNumberError::UnexpectedEnd { position: 2 }));
+    assert!(matches!(error("1 - "), ParseNumberError::UnexpectedEnd { position: 4 }));
+    assert!(matches!(error("1 2"), ParseNumberError::UnexpectedChar { position: 2, found: '2' }));
+    assert!(matches!(error("1+2ix"), ParseNumberError::UnexpectedChar { position: 4, found: 'x' }));
+    assert!(matches!(error("2+-3i"), ParseNumberError::UnexpectedChar { position: 2, found: '-' }));
+    assert!(matches!(error("1.2.3"), ParseNumberError::UnexpectedChar { position: 3, found: '.' }));
+    assert!(matches!(error("1e"), ParseNumberError::UnexpectedChar { position: 1, found: 'e' }));
+    assert!(matches!(error("3k"), ParseNumberError::UnexpectedChar { position: 1, found: 'k' }));
+    assert!(matches!(error("1+2i+3j"), ParseNumberError::RepeatedPart { position: 4 }));
+    assert!(matches!(error("1 + 2 + i"), ParseNumberError::RepeatedPart { position: 2 }));
+    match error("1+.i") {
+        ParseNumberError::InvalidNumber { position, text } => assert!(position == 2 && text == "."),
+        other => panic!("unexpected {}", other)
+    }
+    //positions count characters, not bytes
+    assert!(matches!(error("é+1"), ParseNumberError::UnexpectedChar { position: 0, found: 'é' }));
+    assert!(matches!(error("1+2ié"), ParseNumberError::UnexpectedChar { position: 4, found: 'é' }));
+    let error = |s: &str| s.parse::<Quaternion>().err().unwrap();
+    assert!(matches!(error("i+j-i"), ParseNumberError::RepeatedPart { position: 3 }));
+    assert!(matches!(error("1+2j k"), ParseNumberError::UnexpectedChar { position: 5, found: 'k' }));
+    assert!(error("1+2il").to_string() == "unexpected 'l' at 4");
+}
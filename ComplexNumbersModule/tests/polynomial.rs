@@ -0,0 +1,144 @@
+use ComplexNumbersModule::*;
+
+//every expected root is within tolerance of its own found root, multiplicities included
+fn same_roots(found: &[ComplexNumber], expected: &[ComplexNumber], tolerance: f64) -> bool {
+    let mut unused: Vec<ComplexNumber> = found.to_vec();
+    found.len() == expected.len() && expected.iter().all(|&r| {
+        match unused.iter().position(|&z| (z - r).abs() <= tolerance) {
+            Some(k) => {
+                unused.swap_remove(k);
+                true
+            },
+            None => false
+        }
+    })
+}
+
+fn reals(roots: &[f64]) -> Vec<ComplexNumber> {
+    roots.iter().map(|&r| complex!(r, 0)).collect()
+}
+
+#[test]
+fn roots_of_known_factorizations() {
+    let expected = [1.0, -2.0, 3.5, 0.25, -7.0];
+    let p = Polynomial::from_roots(&expected);
+    assert!(same_roots(&p.roots(), &reals(&expected), 1e-12), "{:?}", p.roots());
+    //x^2 + 1 and x^5 - 1 only have complex roots off the real line
+    assert!(same_roots(&Polynomial::new(vec![1.0, 0.0, 1.0]).roots(), &[complex!(0, 1), complex!(0, -1)], 1e-14));
+    let unity: Vec<ComplexNumber> = (0..5).map(|k| ComplexNumber::from_polar(1.0, 2.0 * std::f64::consts::PI * k as f64 / 5.0)).collect();
+    assert!(same_roots(&Polynomial::new(vec![-1.0, 0.0, 0.0, 0.0, 0.0, 1.0]).roots(), &unity, 1e-14));
+    //complex coefficients
+    let expected = [complex!(1, 1), complex!(0, -2), complex!(3, 0), complex!(-1, -0.5)];
+    let p = Polynomial::from_roots(&expected);
+    assert!(same_roots(&p.roots(), &expected, 1e-12));
+    assert!(p.roots().iter().all(|&z| p.eval(z).abs() < 1e-12));
+    //wilkinson's polynomial of degree 10 is badly conditioned, but still converges
+    let expected: Vec<f64> = (1..=10).map(|k| k as f64).collect();
+    assert!(same_roots(&Polynomial::from_roots(&expected).roots(), &reals(&expected), 1e-8));
+}
+
+#[test]
+fn multiple_roots() {
+    //an m-fold root is only found to about the m-th root of the precision
+    let double = Polynomial::from_roots(&[2.0, 2.0, -1.0]);
+    assert!(same_roots(&double.roots(), &reals(&[2.0, 2.0, -1.0]), 1e-7));
+    let triple = Polynomial::from_roots(&[1.0, 1.0, 1.0, -2.0]);
+    assert!(same_roots(&triple.roots(), &reals(&[1.0, 1.0, 1.0, -2.0]), 1e-4));
+    let pair = Polynomial::from_roots(&[complex!(1, 1), complex!(1, 1), complex!(1, -1), complex!(1, -1)]);
+    assert!(same_roots(&pair.roots(), &[complex!(1, 1), complex!(1, 1), complex!(1, -1), complex!(1, -1)], 1e-6));
+}
+
+#[test]
+fn zero_coefficients() {
+    //zeros above the leading coefficient are dropped
+    let p = Polynomial::new(vec![2.0, -3.0, 1.0, 0.0, 0.0]);
+    assert!(p.degree() == Some(2) && p.coeffs() == [2.0, -3.0, 1.0]);
+    assert!(same_roots(&p.roots(), &reals(&[1.0, 2.0]), 1e-14));
+    //zeros at the bottom are exact roots at the origin
+    let p = Polynomial::new(vec![0.0, 0.0, 0.0, -1.0, 1.0]);
+    let roots = p.roots();
+    assert!(roots.iter().filter(|z| z.is_zero()).count() == 3 && same_roots(&roots, &reals(&[0.0, 0.0, 0.0, 1.0]), 1e-14));
+    assert!(Polynomial::new(vec![0.0, 0.0, 3.0]).roots() == [complex!(0, 0), complex!(0, 0)]);
+    assert!(Polynomial::<f64>::x().roots() == [complex!(0, 0)]);
+    assert!(Polynomial::new(vec![-3.0, 2.0]).roots() == [complex!(1.5, 0)]);
+    //constants, zero included, have no roots
+    assert!(Polynomial::constant(4.0).roots().is_empty());
+    assert!(Polynomial::new(vec![0.0, 0.0]).is_zero() && Polynomial::<f64>::zero().roots().is_empty());
+    assert!(Polynomial::<f64>::zero().degree().is_none());
+}
+
+#[test]
+fn real_roots() {
+    let p = Polynomial::from_roots(&[3.0, -0.5]) * Polynomial::new(vec![1.0, 0.0, 1.0]);
+    let mut roots = p.real_roots(1e-9);
+    roots.sort_by(f64::total_cmp);
+    assert!(roots.len() == 2 && (roots[0] + 0.5).abs() < 1e-12 && (roots[1] - 3.0).abs() < 1e-12);
+    assert!(Polynomial::new(vec![1.0, 0.0, 1.0]).real_roots(1e-9).is_empty());
+    assert!(Polynomial::from_roots(&[1.0, 2.0, 3.0]).real_roots(1e-9).len() == 3);
+}
+
+#[test]
+fn division() {
+    //x^3 - 2x^2 - 4 = (x - 3)(x^2 + x + 3) + 5
+    let p = Polynomial::new(vec![-4.0, 0.0, -2.0, 1.0]);
+    let d = Polynomial::new(vec![-3.0, 1.0]);
+    let (q, r) = p.div_rem(&d);
+    assert!(q == Polynomial::new(vec![3.0, 1.0, 1.0]) && r == Polynomial::constant(5.0));
+    assert!(&(&q * &d) + &r == p);
+    assert!(p.clone() / d.clone() == q && p.clone() % d.clone() == r);
+    //an exact factor leaves no remainder
+    let (q, r) = Polynomial::from_roots(&[1.0, 2.0, 3.0]).div_rem(&Polynomial::from_roots(&[2.0]));
+    assert!(q == Polynomial::from_roots(&[1.0, 3.0]) && r.is_zero());
+    //a divisor of higher degree gives a zero quotient, a constant one scales
+    let (q, r) = d.div_rem(&p);
+    assert!(q.is_zero() && r == d);
+    let (q, r) = p.div_rem(&Polynomial::constant(2.0));
+    assert!(q == p.clone() * 0.5 && r.is_zero());
+    let (q, r) = Polynomial::<f64>::zero().div_rem(&d);
+    assert!(q.is_zero() && r.is_zero());
+}
+
+#[test]
+#[should_panic(expected = "polynomial division by zero")]
+fn division_by_the_zero_polynomial() {
+    Polynomial::new(vec![1.0, 2.0]).div_rem(&Polynomial::zero());
+}
+
+#[test]
+fn derivative() {
+    //3x^3 - x + 5 gives 9x^2 - 1
+    let p = Polynomial::new(vec![5.0, -1.0, 0.0, 3.0]);
+    assert!(p.derivative() == Polynomial::new(vec![-1.0, 0.0, 9.0]));
+    assert!(p.derivative().derivative().derivative() == Polynomial::constant(18.0));
+    assert!(Polynomial::constant(7.0).derivative().is_zero() && Polynomial::<f64>::zero().derivative().is_zero());
+    //over the integers too
+    assert!(Polynomial::new(vec![1i64, 2, 3]).derivative() == Polynomial::new(vec![2i64, 6]));
+}
+
+#[test]
+fn companion() {
+    //x^3 - 6x^2 + 11x - 6 has roots 1, 2 and 3
+    let p = Polynomial::from_roots(&[1.0, 2.0, 3.0]);
+    let c = p.companion();
+    assert!(c == matrix!(3, 3, vec![vec![0.0, 0.0, 6.0], vec![1.0, 0.0, -11.0], vec![0.0, 1.0, 6.0]]));
+    let eigen = c.eigen().unwrap();
+    assert!(same_roots(&eigen.values, &reals(&[1.0, 2.0, 3.0]), 1e-10));
+    //the coefficients are made monic first
+    assert!((p.clone() * 2.0).companion() == c);
+    assert!(Polynomial::constant(3.0).companion().shape() == (0, 0));
+    assert!(Polynomial::new(vec![4.0, 2.0]).companion() == matrix!(1, 1, vec![vec![-2.0]]));
+}
+
+#[test]
+fn display() {
+    assert_eq!(Polynomial::new(vec![1.0, -3.0, 2.0]).to_string(), "2x^2 - 3x + 1");
+    assert_eq!(Polynomial::new(vec![0.0, 1.0, 0.0, -1.0]).to_string(), "-x^3 + x");
+    assert_eq!(Polynomial::new(vec![1.5, 0.0, -0.25]).to_string(), "-0.25x^2 + 1.5");
+    assert_eq!(Polynomial::constant(-1.0).to_string(), "-1");
+    assert_eq!(Polynomial::<f64>::x().to_string(), "x");
+    assert_eq!(Polynomial::<f64>::zero().to_string(), "0");
+    //coefficients with an inner sign are bracketed
+    let p = Polynomial::new(vec![complex!(0, 1), complex!(1, 2), complex!(0, -1)]);
+    assert_eq!(p.to_string(), "-ix^2 + (1+2i)x + i");
+    assert_eq!(format!("{:?}", Polynomial::new(vec![-2i64, 0, 1])), "x^2 - 2");
+}
@@ -0,0 +1,294 @@
+use ComplexNumbersModule::*;
+use ComplexNumbersModule::quaternion::norm3;
+use std::f64::consts::PI;
+
+const AXES: [EulerAxes; 12] = [
+    EulerAxes::XYZ, EulerAxes::XZY, EulerAxes::YXZ, EulerAxes::YZX, EulerAxes::ZXY, EulerAxes::ZYX,
+    EulerAxes::XYX, EulerAxes::XZX, EulerAxes::YXY, EulerAxes::YZY, EulerAxes::ZXZ, EulerAxes::ZYZ
+];
+
+fn sequences() -> Vec<EulerSequence> {
+    AXES.iter().flat_map(|&axes| [EulerSequence::Intrinsic(axes), EulerSequence::Extrinsic(axes)]).collect()
+}
+
+//q and -q are the same rotation
+fn same_rotation(p: Quaternion<f64>, q: Quaternion<f64>) -> bool {
+    p.dot(&q).abs() > 1.0 - 1e-12
+}
+
+#[test]
+fn euler_round_trip() {
+    //the gimbal lock angles of both kinds of sequence and a few generic ones
+    let angles = [0.0, PI / 2.0, -PI / 2.0, PI, 0.3, -1.2, 2.5, -2.9];
+    for seq in sequences() {
+        let proper = seq.axes().indices()[0] == seq.axes().indices()[2];
+        for &a in &angles {
+            for &b in &angles {
+                for &c in &angles {
+                    let q = Quaternion::from_euler(seq, a, b, c);
+                    let [x, y, z] = q.to_euler(seq);
+                    let back = Quaternion::from_euler(seq, x, y, z);
+                    assert!(same_rotation(q, back), "{:?} {:?} gave {:?}", [a, b, c], q, [x, y, z]);
+                    assert!([x, y, z].iter().all(|t| *t > -PI && *t <= PI), "{:?} {:?}", [a, b, c], [x, y, z]);
+                    if proper {
+                        assert!((0.0..=PI).contains(&y));
+                    } else {
+                        assert!((-PI / 2.0..=PI / 2.0).contains(&y));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn euler_angles_recovered() {
+    //away from gimbal lock the angles themselves come back
+    for seq in sequences() {
+        let proper = seq.axes().indices()[0] == seq.axes().indices()[2];
+        let (a, b, c) = (0.4, if proper {1.1} else {-0.7}, -2.2);
+        let got = Quaternion::from_euler(seq, a, b, c).to_euler(seq);
+        for (x, y) in got.iter().zip([a, b, c]) {
+            assert!((x - y).abs() < 1e-12, "{:?} gave {:?}", [a, b, c], got);
+        }
+    }
+}
+
+#[test]
+fn euler_gimbal_lock_sets_third_angle() {
+    //only the sum or difference of the outer angles is defined, the third comes out zero
+    for seq in sequences() {
+        let proper = seq.axes().indices()[0] == seq.axes().indices()[2];
+        for b in if proper {[0.0, PI]} else {[PI / 2.0, -PI / 2.0]} {
+            let [_, _, z] = Quaternion::from_euler(seq, 0.5, b, 0.25).to_euler(seq);
+            assert!(z.abs() < 1e-12);
+        }
+    }
+}
+
+fn close3(a: [f64; 3], b: [f64; 3]) -> bool {
+    a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-12)
+}
+
+#[test]
+fn rotations_of_known_vectors() {
+    //a quarter turn about z takes x to y and y to -x
+    let q = Quaternion::from_axis_angle([0.0, 0.0, 1.0], PI / 2.0);
+    assert!(close3(q.rotate_vector([1.0, 0.0, 0.0]), [0.0, 1.0, 0.0]));
+    assert!(close3(q.rotate_vector([0.0, 1.0, 0.0]), [-1.0, 0.0, 0.0]));
+    assert!(close3(q.rotate_vector([0.0, 0.0, 2.0]), [0.0, 0.0, 2.0]));
+    //a third of a turn about (1, 1, 1) cycles the axes, the axis need not be unit length
+    let q = Quaternion::from_axis_angle([2.0, 2.0, 2.0], 2.0 * PI / 3.0);
+    assert!(close3(q.rotate_vector([1.0, 0.0, 0.0]), [0.0, 1.0, 0.0]));
+    assert!(close3(q.rotate_vector([0.0, 0.0, 3.0]), [3.0, 0.0, 0.0]));
+    let (axis, angle) = q.to_axis_angle();
+    let third = 1.0 / 3f64.sqrt();
+    assert!(close3(axis, [third, third, third]) && (angle - 2.0 * PI / 3.0).abs() < 1e-12);
+    //the matrix rotates the same way as the quaternion
+    let m = q.to_rotation_matrix();
+    let v = [0.3, -1.2, 2.0];
+    let mv = [0, 1, 2].map(|x| (0..3).map(|y| m[(x, y)] * v[y]).sum::<f64>());
+    assert!(close3(mv, q.rotate_vector(v)));
+}
+
+#[test]
+fn rotation_matrix_round_trip() {
+    //half turns have trace -1 and go through each of the x, y and z branches of Shepperd's method,
+    //the rest cover the w branch and axes in between
+    let rotations = [
+        ([1.0, 0.0, 0.0], PI), ([0.0, 1.0, 0.0], PI), ([0.0, 0.0, 1.0], PI),
+        ([1.0, 2.0, 0.5], 3.0), ([-0.2, 1.0, 0.1], 2.8), ([0.3, 0.1, -1.0], 2.9),
+        ([1.0, -1.0, 2.0], 0.4), ([0.0, 0.0, 1.0], 0.0)
+    ];
+    for (axis, angle) in rotations {
+        let q = Quaternion::from_axis_angle(axis, angle);
+        let m = q.to_rotation_matrix();
+        let back = Quaternion::from_rotation_matrix(&m);
+        assert!(same_rotation(q, back), "{:?} {} gave {:?}", axis, angle, back);
+        assert!(back.Re >= 0.0 && (back.norm() - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+#[should_panic(expected = "a rotation matrix is 3x3, got 4x4")]
+fn rotation_matrix_must_be_3x3() {
+    Quaternion::<f64>::from_rotation_matrix(&Matrix::identitymat(4));
+}
+
+#[test]
+#[should_panic(expected = "a rotation matrix is 3x3, got 2x2")]
+fn rotation_matrix_too_small() {
+    Quaternion::<f64>::from_rotation_matrix(&Matrix::identitymat(2));
+}
+
+fn close(p: Quaternion<f64>, q: Quaternion<f64>, tolerance: f64) -> bool {
+    (p - q).norm() < tolerance
+}
+
+fn about_z(angle: f64) -> Quaternion<f64> {
+    Quaternion::from_axis_angle([0.0, 0.0, 1.0], angle)
+}
+
+#[test]
+fn slerp_endpoints_and_speed() {
+    let a = Quaternion::from_axis_angle([1.0, 2.0, 0.5], 0.3);
+    let b = Quaternion::from_axis_angle([-1.0, 0.5, 1.0], 1.7);
+    assert!(close(Quaternion::slerp(a, b, 0.0), a, 1e-12));
+    assert!(close(Quaternion::slerp(a, b, 1.0), b, 1e-12));
+    assert!(close(Quaternion::nlerp(a, b, 0.0), a, 1e-12));
+    assert!(close(Quaternion::nlerp(a, b, 1.0), b, 1e-12));
+    //constant angular speed along the arc
+    let total = a.dot(&b).acos();
+    for t in [0.1, 0.25, 0.5, 0.9] {
+        let q = Quaternion::slerp(a, b, t);
+        assert!((q.norm() - 1.0).abs() < 1e-12);
+        assert!((a.dot(&q).acos() - t * total).abs() < 1e-12);
+    }
+    //rotations along the same axis just add their angles
+    assert!(close(Quaternion::slerp(about_z(0.2), about_z(1.4), 0.5), about_z(0.8), 1e-12));
+}
+
+#[test]
+fn slerp_takes_the_short_way() {
+    //-q is the same rotation as q, the halfway point must be the 45 degree turn and not 135 degrees the other way
+    let a = Quaternion::one();
+    let b = -about_z(PI / 2.0);
+    assert!(a.dot(&b) < 0.0);
+    assert!(close(Quaternion::slerp(a, b, 0.5), about_z(PI / 4.0), 1e-12));
+    assert!(same_rotation(Quaternion::slerp(a, b, 1.0), b));
+    assert!(same_rotation(Quaternion::nlerp(a, b, 0.5), about_z(PI / 4.0)));
+}
+
+#[test]
+fn slerp_nearly_parallel() {
+    //a thousandth of a radian apart, slerp falls back to normalized linear interpolation
+    let (a, b) = (about_z(0.5), about_z(0.501));
+    assert!(a.dot(&b) > 0.9995);
+    for t in [0.0, 0.3, 0.5, 1.0] {
+        let q = Quaternion::slerp(a, b, t);
+        assert!((q.norm() - 1.0).abs() < 1e-12);
+        assert!(close(q, about_z(0.5 + 0.001 * t), 1e-9));
+    }
+    assert!(close(Quaternion::slerp(a, a, 0.7), a, 1e-15));
+}
+
+#[test]
+fn squad_through_keyframes() {
+    let keys = [
+        Quaternion::one(),
+        Quaternion::from_axis_angle([1.0, 0.0, 0.0], 1.0),
+        -Quaternion::from_axis_angle([1.0, 1.0, 0.0], 2.0),
+        Quaternion::from_axis_angle([0.0, 1.0, 1.0], 0.5)
+    ];
+    for (k, key) in keys.iter().enumerate() {
+        assert!(same_rotation(Quaternion::squad_path(&keys, k as f64), *key), "key {}", k);
+    }
+    //no jumps between segments or across the flipped key
+    let mut previous = Quaternion::squad_path(&keys, 0.0);
+    for step in 1..=300 {
+        let q = Quaternion::squad_path(&keys, step as f64 * 0.01);
+        assert!((q.norm() - 1.0).abs() < 1e-12);
+        assert!(close(q, previous, 0.05), "jump at {}", step as f64 * 0.01);
+        previous = q;
+    }
+    //out of range parameters clamp to the ends
+    assert!(same_rotation(Quaternion::squad_path(&keys, -1.0), keys[0]));
+    assert!(same_rotation(Quaternion::squad_path(&keys, 9.0), keys[3]));
+    assert!(close(Quaternion::squad_path(&keys[..1], 0.5), keys[0], 1e-15));
+    //with the controls on the endpoints squad is plain slerp
+    let (a, b) = (keys[1], keys[3]);
+    assert!(close(Quaternion::squad(a, b, a, b, 0.3), Quaternion::slerp(a, b, 0.3), 1e-12));
+}
+
+#[test]
+fn exp_inverts_ln() {
+    for q in [quaternion!(1, 2, 3, 4), quaternion!(-0.5, 0.1, -0.2, 0.05), quaternion!(0, 0, 0, 3), quaternion!(2, 0, 0, 0), quaternion!(-3, 0, 0, 0)] {
+        let back = q.ln().exp();
+        assert!(close(back, q, 1e-12 * q.norm()), "{:?} came back as {:?}", q, back);
+    }
+    //the vector part of ln has length at most pi
+    let l = quaternion!(-1, 1e-3, 0, 0).ln();
+    assert!(norm3([l.Im, l.Jm, l.Km]) <= PI);
+}
+
+#[test]
+fn integer_powers_match_repeated_products() {
+    let q = quaternion!(0.5, -1, 0.25, 2);
+    let mut product = Quaternion::one();
+    for n in 1..=6 {
+        product *= q;
+        let tolerance = 1e-12 * product.norm();
+        assert!(close(q.powf(n as f64), product, tolerance), "q^{}", n);
+        assert!(close(q.pow(quaternion!(n, 0, 0, 0)), product, tolerance), "q^{}", n);
+    }
+    assert!(close(q.powf(-2.0), (q * q).inverse(), 1e-12));
+    assert!(close(q.powf(0.0), Quaternion::one(), 1e-15));
+    let root = q.powf(0.5);
+    assert!(close(root * root, q, 1e-12));
+}
+
+#[test]
+fn real_quaternions() {
+    //a zero vector part behaves like the real or complex function
+    assert!(close(quaternion!(2, 0, 0, 0).exp(), quaternion!(2f64.exp(), 0, 0, 0), 1e-15));
+    assert!(close(quaternion!(2, 0, 0, 0).ln(), quaternion!(2f64.ln(), 0, 0, 0), 1e-15));
+    assert!(close(Quaternion::<f64>::zero().exp(), Quaternion::one(), 1e-15));
+    assert!(close(Quaternion::<f64>::one().ln(), Quaternion::zero(), 1e-15));
+    //negative reals take the i axis, like complex numbers
+    assert!(close(quaternion!(-1, 0, 0, 0).ln(), quaternion!(0, PI, 0, 0), 1e-15));
+    assert!(close(quaternion!(-4, 0, 0, 0).powf(0.5), quaternion!(0, 2, 0, 0), 1e-15));
+    assert!(close(quaternion!(4, 0, 0, 0).powf(0.5), quaternion!(2, 0, 0, 0), 1e-15));
+    //powers of zero
+    let zero = Quaternion::<f64>::zero();
+    assert!(close(zero.powf(0.0), Quaternion::one(), 1e-15));
+    assert!(close(zero.powf(2.0), zero, 1e-15));
+    assert!(zero.powf(-1.0).Re.is_infinite());
+    assert!(close(zero.pow(quaternion!(2, 0, 0, 0)), zero, 1e-15));
+    assert!(zero.pow(quaternion!(1, 1, 0, 0)).Re.is_nan());
+}
+
+#[test]
+fn single_precision_conversions() {
+    let q: Quaternion<f32> = quaternion!(1, 0.1, -2.5, 3e-8; f32);
+    assert!(q.Re == 1.0f32 && q.Im == 0.1f32 && q.Jm == -2.5f32 && q.Km == 3e-8f32);
+    //widening is exact and narrowing it again gives back the same bits
+    let wide: Quaternion = q.into();
+    assert!(wide == quaternion!(1.0, 0.1f32, -2.5, 3e-8f32));
+    assert!(wide.Im != 0.1);
+    assert!(wide.to_f32() == q);
+    assert!(q.cast::<f64>() == wide && wide.cast::<f32>() == q && q.cast::<f32>() == q);
+    //narrowing rounds to the nearest f32
+    let p = quaternion!(0.1, 1.0 + 1e-10, -1e-50, 1e40);
+    let narrow = p.to_f32();
+    assert!(narrow.Im == 1.0 && narrow.Jm == 0.0 && narrow.Jm.is_sign_negative() && narrow.Km.is_infinite());
+    assert!(p.cast::<f32>() == narrow);
+    assert!(Quaternion::<f64>::from(narrow).Re == 0.1f32 as f64);
+    assert!(p.cast::<f64>() == p);
+    //the f32 arithmetic works on its own
+    let product = q * q.inverse() - Quaternion::<f32>::one();
+    assert!(product.norm() < 1e-6);
+}
+
+#[test]
+fn left_and_right_division() {
+    let (i, j, k) = (quaternion!(0, 1, 0, 0), quaternion!(0, 0, 1, 0), quaternion!(0, 0, 0, 1));
+    //i j^-1 = -i j = -k but j^-1 i = -j i = k
+    assert!(close(i.div_right(j), -k, 1e-15) && close(i / j, -k, 1e-15));
+    assert!(close(i.div_left(j), k, 1e-15));
+    let a = quaternion!(1, -2, 0.5, 3);
+    let b = quaternion!(0.25, 1, -1, 2);
+    assert!(!close(a.div_left(b), a.div_right(b), 1e-3));
+    assert!(close(a.div_right(b) * b, a, 1e-12) && close(b * a.div_left(b), a, 1e-12));
+    assert!(close(a.checked_div_right(b).unwrap(), a.div_right(b), 1e-15));
+    assert!(close(a.checked_div_left(b).unwrap(), a.div_left(b), 1e-15));
+    //commuting operands give one quotient
+    let c = quaternion!(2, 0, 0, 0);
+    assert!(close(a.div_left(c), a.div_right(c), 1e-15));
+    let inverse = b.checked_inverse().unwrap();
+    assert!(close(inverse * b, Quaternion::one(), 1e-15) && close(b * inverse, Quaternion::one(), 1e-15));
+    let zero = Quaternion::<f64>::zero();
+    assert!(zero.checked_inverse().is_none());
+    assert!(a.checked_div_right(zero).is_none() && a.checked_div_left(zero).is_none());
+    assert!(zero.checked_div_right(b).unwrap() == zero);
+    assert!(quaternion!(0, 0, 0, 0; f32).checked_inverse().is_none());
+}